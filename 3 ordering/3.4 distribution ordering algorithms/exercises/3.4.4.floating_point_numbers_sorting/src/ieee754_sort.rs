@@ -0,0 +1,312 @@
+//! THIS SORT GOES WITH LSD RADIXSORT ON IEEE-754 BIT PATTERNS
+//! BUCKETS ARE BOUNDED BY RADIX, NOT BY MANTISSA WIDTH
+//!
+//! IEEE-754 binary layout
+//! +------+----------+----------+
+//! |      | exponent | fraction |
+//! +------+----------+----------+
+//! | f32  |  8 bit   |  23 bit  |
+//! | f64  |  11 bit  |  52 bit  |
+//! +------+----------+----------+
+//! sign bit leads both
+//!
+//! for positives bits order matches value order, only sign bit must be set
+//! to move them above negatives
+//! for negatives bits order is reversed, so all bits are flipped
+//! resulting unsigned order equals `total_cmp` order
+//! -NaN < -∞ < … < -0.0 < +0.0 < … < +∞ < +NaN
+
+use radixsort::radix_key::{sort_by_radix_key, RadixKey};
+
+/// NaN placement
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Nans {
+    /// as `total_cmp`, NaNs with sign bit first, others last
+    TotalOrder,
+    /// all NaNs first
+    First,
+    /// all NaNs last
+    Last,
+}
+
+/// -0.0 and +0.0 relation
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Zeros {
+    /// as `total_cmp`, -0.0 < +0.0
+    Distinct,
+    /// -0.0 = +0.0, original order of zeros is kept
+    Equal,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Options {
+    pub nans: Nans,
+    pub zeros: Zeros,
+}
+
+impl Default for Options {
+    /// Order of `total_cmp`.
+    fn default() -> Self {
+        Options {
+            nans: Nans::TotalOrder,
+            zeros: Zeros::Distinct,
+        }
+    }
+}
+
+pub trait IeeeFloat: Copy {
+    type Key: RadixKey;
+
+    /// +0.0
+    const ZERO: Self;
+
+    /// Unsigned key of `total_cmp` order.
+    fn total_key(&self) -> Self::Key;
+    fn is_nan(&self) -> bool;
+    fn is_zero(&self) -> bool;
+}
+
+macro_rules! ieee_float {
+    ($($f:ty => $u:ty),*) => {
+        $(
+            impl IeeeFloat for $f {
+                type Key = $u;
+
+                const ZERO: $f = 0.0;
+
+                fn total_key(&self) -> $u {
+                    const SIGN: $u = 1 << (<$u>::BITS - 1);
+
+                    let bits = self.to_bits();
+                    if bits & SIGN == SIGN {
+                        !bits
+                    } else {
+                        bits | SIGN
+                    }
+                }
+
+                fn is_nan(&self) -> bool {
+                    <$f>::is_nan(*self)
+                }
+
+                fn is_zero(&self) -> bool {
+                    *self == 0.0
+                }
+            }
+        )*
+    };
+}
+
+ieee_float!(f32 => u32, f64 => u64);
+
+/// Stable LSD radixsort of `f32`/`f64`
+/// # Summary
+/// With `Options::default()` result equals `sort_by(total_cmp)`.
+/// <br/>T: Θ(⌈bits / radix⌉ × n)
+/// <br/>S: Θ(n + 2ʳᵃᵈⁱˣ)
+pub fn sort<F: IeeeFloat>(fpoints: &mut [F], opts: Options) {
+    match opts.zeros {
+        Zeros::Distinct => sort_by_radix_key(fpoints, |f| f.total_key()),
+        Zeros::Equal => sort_by_radix_key(fpoints, |f| {
+            // -0.0 gets key of +0.0
+            if f.is_zero() {
+                F::ZERO.total_key()
+            } else {
+                f.total_key()
+            }
+        }),
+    }
+
+    // sorted as total order, thus
+    // [-NaNs][numbers][+NaNs]
+    let len = fpoints.len();
+    let neg_nans = fpoints.iter().take_while(|f| f.is_nan()).count();
+    let pos_nans = if neg_nans == len {
+        0
+    } else {
+        fpoints.iter().rev().take_while(|f| f.is_nan()).count()
+    };
+
+    match opts.nans {
+        Nans::TotalOrder => {}
+        Nans::First => fpoints[neg_nans..].rotate_right(pos_nans),
+        Nans::Last => fpoints[..len - pos_nans].rotate_left(neg_nans),
+    }
+}
+
+#[cfg(test)]
+mod sort_tests {
+    use super::*;
+
+    // xorshift for determinism
+    fn pseudo_random_bits(len: usize) -> Vec<u64> {
+        let mut x = 0x2545_F491_4F6C_DD1Du64;
+        (0..len)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 7;
+                x ^= x << 17;
+                x
+            })
+            .collect()
+    }
+
+    fn specials_f64() -> Vec<f64> {
+        vec![
+            f64::NAN,
+            -f64::NAN,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            0.0,
+            -0.0,
+            f64::MIN_POSITIVE,
+            -f64::MIN_POSITIVE,
+            // subnormal
+            f64::from_bits(1),
+            -f64::from_bits(1),
+            f64::MAX,
+            f64::MIN,
+            1.0,
+            -1.0,
+        ]
+    }
+
+    fn bits_eq<F: IeeeFloat + Into<f64>>(l: &[F], r: &[F]) -> bool {
+        l.len() == r.len()
+            && l.iter()
+                .zip(r.iter())
+                .all(|(a, b)| (*a).into().to_bits() == (*b).into().to_bits())
+    }
+
+    #[test]
+    fn total_order_f64_test() {
+        let mut fpoints = pseudo_random_bits(2_000)
+            .into_iter()
+            .map(f64::from_bits)
+            .chain(specials_f64())
+            .collect::<Vec<f64>>();
+
+        let mut criterion = fpoints.clone();
+        criterion.sort_by(|a, b| a.total_cmp(b));
+
+        sort(&mut fpoints, Options::default());
+        assert!(bits_eq(&criterion, &fpoints));
+    }
+
+    #[test]
+    fn total_order_f32_test() {
+        let mut fpoints = pseudo_random_bits(2_000)
+            .into_iter()
+            .map(|x| f32::from_bits(x as u32))
+            .chain([
+                f32::NAN,
+                -f32::NAN,
+                f32::INFINITY,
+                f32::NEG_INFINITY,
+                0.0,
+                -0.0,
+            ])
+            .collect::<Vec<f32>>();
+
+        let mut criterion = fpoints.clone();
+        criterion.sort_by(|a, b| a.total_cmp(b));
+
+        sort(&mut fpoints, Options::default());
+        assert!(bits_eq(&criterion, &fpoints));
+    }
+
+    #[test]
+    fn basic_test() {
+        let mut fpoints = [3.5, -2.25, 0.0, 1e-300, -1e300, 7.0];
+        sort(&mut fpoints, Options::default());
+        assert_eq!([-1e300, -2.25, 0.0, 1e-300, 3.5, 7.0], fpoints);
+    }
+
+    #[test]
+    fn nans_first_test() {
+        let mut fpoints = [1.0, f64::NAN, -1.0, -f64::NAN, f64::INFINITY];
+        let opts = Options {
+            nans: Nans::First,
+            zeros: Zeros::Distinct,
+        };
+
+        sort(&mut fpoints, opts);
+
+        assert!(fpoints[0].is_nan() && fpoints[0].is_sign_negative());
+        assert!(fpoints[1].is_nan() && fpoints[1].is_sign_positive());
+        assert_eq!([-1.0, 1.0, f64::INFINITY], fpoints[2..]);
+    }
+
+    #[test]
+    fn nans_last_test() {
+        let mut fpoints = [1.0, f64::NAN, -1.0, -f64::NAN, f64::NEG_INFINITY];
+        let opts = Options {
+            nans: Nans::Last,
+            zeros: Zeros::Distinct,
+        };
+
+        sort(&mut fpoints, opts);
+
+        assert_eq!([f64::NEG_INFINITY, -1.0, 1.0], fpoints[..3]);
+        assert!(fpoints[3].is_nan() && fpoints[3].is_sign_negative());
+        assert!(fpoints[4].is_nan() && fpoints[4].is_sign_positive());
+    }
+
+    #[test]
+    fn nans_only_test() {
+        for nans in [Nans::TotalOrder, Nans::First, Nans::Last] {
+            let mut fpoints = [f32::NAN, -f32::NAN];
+            let opts = Options {
+                nans,
+                zeros: Zeros::Distinct,
+            };
+
+            sort(&mut fpoints, opts);
+            assert!(fpoints[0].is_sign_negative());
+            assert!(fpoints[1].is_sign_positive());
+        }
+    }
+
+    #[test]
+    fn zeros_distinct_test() {
+        let mut fpoints = [0.0f64, -0.0, 0.0, -0.0];
+        sort(&mut fpoints, Options::default());
+
+        let signs = fpoints.map(|f| f.is_sign_negative());
+        assert_eq!([true, true, false, false], signs);
+    }
+
+    #[test]
+    fn zeros_equal_test() {
+        let mut fpoints = [0.0f64, 1.0, -0.0, -1.0, 0.0, -0.0];
+        let opts = Options {
+            nans: Nans::TotalOrder,
+            zeros: Zeros::Equal,
+        };
+
+        sort(&mut fpoints, opts);
+
+        // stable, zeros keep original order
+        assert_eq!([-1.0, 0.0, 0.0, 0.0, 0.0, 1.0], fpoints);
+        let signs = fpoints[1..5]
+            .iter()
+            .map(|f| f.is_sign_negative())
+            .collect::<Vec<bool>>();
+        assert_eq!(vec![false, true, false, true], signs);
+    }
+
+    #[test]
+    fn total_key_test() {
+        assert_eq!(0x8000_0000, 0.0f32.total_key());
+        assert_eq!(0x7FFF_FFFF, (-0.0f32).total_key());
+        assert_eq!(0xBF80_0000, 1.0f32.total_key());
+        assert_eq!(!0xBF80_0000u32, (-1.0f32).total_key());
+        assert!(f64::NEG_INFINITY.total_key() < f64::MIN.total_key());
+    }
+
+    #[test]
+    fn empty_test() {
+        let mut fpoints: [f64; 0] = [];
+        sort(&mut fpoints, Options::default());
+    }
+}
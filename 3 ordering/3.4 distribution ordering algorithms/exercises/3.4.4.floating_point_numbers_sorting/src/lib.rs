@@ -1,4 +1,5 @@
 mod auxies;
+pub mod ieee754_sort;
 mod lex_sort;
 mod mem_eff_lex_sort;
 mod poly_sort;
@@ -1,2 +1,3 @@
 mod btree_alpha;
+pub mod uni_alpha;
 mod vec_alpha;
@@ -0,0 +1,364 @@
+use std::collections::{BTreeMap, BTreeSet};
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Symbol strings are sorted on
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Symbols {
+  /// `char`, key is code point itself
+  CodePoints,
+  /// extended grapheme cluster (user perceived character),
+  /// key is rank of cluster among clusters present
+  Graphemes,
+}
+
+/// Alphabet bucket table
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Table {
+  /// `Vec` indexed by key minus least key present
+  Vec,
+  /// `BTreeMap` keyed by key
+  BTree,
+  /// `Vec` for dense alphabet, `BTree` otherwise
+  Auto,
+}
+
+// alphabet is dense when it occupies at least
+// 1 / DENSITY_FACTOR of span between its least and greatest key
+const DENSITY_FACTOR: usize = 4;
+
+/// Stable lexicographic sort on Unicode symbols
+/// # Summary
+/// Alphabet is set of symbols actually present in `strs`.
+/// <br/>Order for `Symbols::CodePoints` equals `str::cmp`.
+/// <br/>Order for `Symbols::Graphemes` compares clusters as whole, i.e. `"e😀" < "é"`
+/// even when `'é'` is written as `e` + U+0301.
+/// <br/>Empty strings are supported.
+pub fn sort(strs: &mut [&str], symbs: Symbols, table: Table) {
+  if strs.len() < 2 {
+    return;
+  }
+
+  let (keys, alpha) = match symbs {
+    Symbols::CodePoints => code_point_keys(strs),
+    Symbols::Graphemes => grapheme_keys(strs),
+  };
+
+  // only empty strings
+  if alpha.len == 0 {
+    return;
+  }
+
+  let ixs = match choose(table, &alpha) {
+    Table::Vec => sort_focus(&keys, &mut VecBuckets::new(&alpha)),
+    _ => sort_focus(&keys, &mut BTreeBuckets(BTreeMap::new())),
+  };
+
+  let sorted = ixs.iter().map(|ix| strs[*ix]).collect::<Vec<&str>>();
+  strs.copy_from_slice(&sorted);
+}
+
+struct Alphabet {
+  // least key
+  min: usize,
+  // greatest key
+  max: usize,
+  // count of distinct keys
+  len: usize,
+}
+
+impl Alphabet {
+  fn span(&self) -> usize {
+    self.max - self.min + 1
+  }
+}
+
+fn choose(table: Table, alpha: &Alphabet) -> Table {
+  match table {
+    Table::Auto => {
+      if alpha.span() / DENSITY_FACTOR <= alpha.len {
+        Table::Vec
+      } else {
+        Table::BTree
+      }
+    }
+    t => t,
+  }
+}
+
+fn code_point_keys(strs: &[&str]) -> (Vec<Vec<usize>>, Alphabet) {
+  let keys = strs
+    .iter()
+    .map(|s| s.chars().map(|c| c as usize).collect::<Vec<usize>>())
+    .collect::<Vec<Vec<usize>>>();
+
+  let distinct = keys.iter().flatten().collect::<BTreeSet<&usize>>();
+  let alpha = Alphabet {
+    min: distinct.first().map_or(0, |k| **k),
+    max: distinct.last().map_or(0, |k| **k),
+    len: distinct.len(),
+  };
+
+  (keys, alpha)
+}
+
+fn grapheme_keys(strs: &[&str]) -> (Vec<Vec<usize>>, Alphabet) {
+  let clusters = strs
+    .iter()
+    .map(|s| s.graphemes(true).collect::<Vec<&str>>())
+    .collect::<Vec<Vec<&str>>>();
+
+  // cluster ranks keep `str::cmp` order of clusters
+  let ranks = clusters
+    .iter()
+    .flatten()
+    .collect::<BTreeSet<&&str>>()
+    .into_iter()
+    .enumerate()
+    .map(|(rank, g)| (*g, rank))
+    .collect::<BTreeMap<&str, usize>>();
+
+  let keys = clusters
+    .iter()
+    .map(|gs| gs.iter().map(|g| ranks[g]).collect::<Vec<usize>>())
+    .collect::<Vec<Vec<usize>>>();
+
+  let alpha = Alphabet {
+    min: 0,
+    max: ranks.len().saturating_sub(1),
+    len: ranks.len(),
+  };
+
+  (keys, alpha)
+}
+
+trait AlphaBuckets {
+  fn push(&mut self, key: usize, ix: usize);
+  /// Moves bucket contents into `output` in key order.
+  fn flush(&mut self, output: &mut Vec<usize>);
+}
+
+struct VecBuckets {
+  offset: usize,
+  bucs: Vec<Vec<usize>>,
+}
+
+impl VecBuckets {
+  fn new(alpha: &Alphabet) -> VecBuckets {
+    VecBuckets {
+      offset: alpha.min,
+      bucs: (0..alpha.span()).map(|_| Vec::new()).collect(),
+    }
+  }
+}
+
+impl AlphaBuckets for VecBuckets {
+  fn push(&mut self, key: usize, ix: usize) {
+    self.bucs[key - self.offset].push(ix);
+  }
+
+  fn flush(&mut self, output: &mut Vec<usize>) {
+    // `append` keeps bucket capacity
+    // so buckets are allocated once for all rounds
+    for buc in self.bucs.iter_mut() {
+      output.append(buc);
+    }
+  }
+}
+
+struct BTreeBuckets(BTreeMap<usize, Vec<usize>>);
+
+impl AlphaBuckets for BTreeBuckets {
+  fn push(&mut self, key: usize, ix: usize) {
+    self.0.entry(key).or_default().push(ix);
+  }
+
+  fn flush(&mut self, output: &mut Vec<usize>) {
+    // only symbols present in round are visited
+    for (_, mut buc) in std::mem::take(&mut self.0) {
+      output.append(&mut buc);
+    }
+  }
+}
+
+// returns indexes of `keys` in sorted order
+fn sort_focus<B: AlphaBuckets>(keys: &[Vec<usize>], alpha_bucs: &mut B) -> Vec<usize> {
+  // TC: ϴ(n)
+  let len_max = keys.iter().map(|k| k.len()).max().unwrap();
+
+  // 0-length bucket holds empty strings
+  let mut len_bucs = (0..=len_max)
+    .map(|_| Vec::new())
+    .collect::<Vec<Vec<usize>>>();
+  for (ix, k) in keys.iter().enumerate() {
+    len_bucs[k.len()].push(ix);
+  }
+
+  let mut output = Vec::<usize>::with_capacity(keys.len());
+
+  // strings ending at `sym_ix` go first so
+  // prefix orders before its extensions
+  for sym_ix in (0..len_max).rev() {
+    for ix in len_bucs[sym_ix + 1].iter().chain(output.iter()) {
+      alpha_bucs.push(keys[*ix][sym_ix], *ix);
+    }
+
+    output.clear();
+    alpha_bucs.flush(&mut output);
+  }
+
+  let mut sorted = std::mem::take(&mut len_bucs[0]);
+  sorted.append(&mut output);
+  sorted
+}
+
+#[cfg(test)]
+mod tests_of_units {
+  use super::*;
+
+  const CJK: [&str; 10] = [
+    "東京",
+    "北京",
+    "京都",
+    "東",
+    "大阪",
+    "北海道",
+    "東京都",
+    "上海",
+    "서울",
+    "부산",
+  ];
+
+  const EMOJI: [&str; 8] = ["😀", "🙂😀", "🚀", "😀😀", "🇨🇿", "🇨🇦", "👨‍👩‍👧", "👨"];
+
+  fn modes() -> [(Symbols, Table); 6] {
+    [
+      (Symbols::CodePoints, Table::Vec),
+      (Symbols::CodePoints, Table::BTree),
+      (Symbols::CodePoints, Table::Auto),
+      (Symbols::Graphemes, Table::Vec),
+      (Symbols::Graphemes, Table::BTree),
+      (Symbols::Graphemes, Table::Auto),
+    ]
+  }
+
+  #[test]
+  fn cjk_test() {
+    for (symbs, table) in modes() {
+      let mut strs = CJK;
+
+      let mut criterion = strs;
+      criterion.sort();
+
+      sort(&mut strs, symbs, table);
+      assert_eq!(criterion, strs, "{:?} {:?}", symbs, table);
+    }
+  }
+
+  #[test]
+  fn code_point_emoji_test() {
+    for table in [Table::Vec, Table::BTree, Table::Auto] {
+      let mut strs = EMOJI;
+
+      let mut criterion = strs;
+      criterion.sort();
+
+      sort(&mut strs, Symbols::CodePoints, table);
+      assert_eq!(criterion, strs);
+    }
+  }
+
+  #[test]
+  fn grapheme_emoji_test() {
+    for table in [Table::Vec, Table::BTree, Table::Auto] {
+      let mut strs = EMOJI;
+
+      // family emoji is 1 cluster, man emoji being its prefix
+      // orders before it
+      let criterion = ["🇨🇦", "🇨🇿", "👨", "👨‍👩‍👧", "😀", "😀😀", "🙂😀", "🚀"];
+
+      sort(&mut strs, Symbols::Graphemes, table);
+      assert_eq!(criterion, strs);
+    }
+  }
+
+  #[test]
+  fn grapheme_vs_code_point_test() {
+    // 'é' as `e` + combining acute accent (U+0301)
+    let mut strs = ["e\u{301}", "e😀"];
+
+    sort(&mut strs, Symbols::CodePoints, Table::Auto);
+    assert_eq!(["e\u{301}", "e😀"], strs);
+
+    sort(&mut strs, Symbols::Graphemes, Table::Auto);
+    assert_eq!(["e😀", "e\u{301}"], strs);
+  }
+
+  #[test]
+  fn stability_test() {
+    let owned = ["東京", "😀", "東京", "👨‍👩‍👧", "a", "😀", "東京", "👨‍👩‍👧", "", ""].map(String::from);
+
+    for (symbs, table) in modes() {
+      let mut strs = owned.iter().map(|s| s.as_str()).collect::<Vec<&str>>();
+      sort(&mut strs, symbs, table);
+
+      let mut criterion = owned.iter().map(|s| s.as_str()).collect::<Vec<&str>>();
+      // `sort` of std is stable
+      criterion.sort();
+
+      // equal strings must keep their original allocations order
+      let ptrs = |strs: &[&str]| strs.iter().map(|s| s.as_ptr()).collect::<Vec<*const u8>>();
+      assert_eq!(ptrs(&criterion), ptrs(&strs), "{:?} {:?}", symbs, table);
+    }
+  }
+
+  #[test]
+  fn empty_strs_test() {
+    let mut strs = ["b", "", "a", ""];
+    sort(&mut strs, Symbols::CodePoints, Table::Auto);
+    assert_eq!(["", "", "a", "b"], strs);
+
+    let mut strs = ["", ""];
+    sort(&mut strs, Symbols::Graphemes, Table::Auto);
+    assert_eq!(["", ""], strs);
+  }
+
+  #[test]
+  fn prefix_test() {
+    let mut strs = ["東京都", "東", "東京"];
+    sort(&mut strs, Symbols::CodePoints, Table::Auto);
+    assert_eq!(["東", "東京", "東京都"], strs);
+  }
+
+  #[test]
+  fn choose_test() {
+    // hiragana あ–お, 5 distinct in span of 9
+    let (_, alpha) = code_point_keys(&["あいうえお"]);
+    assert_eq!(Table::Vec, choose(Table::Auto, &alpha));
+
+    // ASCII together with CJK
+    let (_, alpha) = code_point_keys(&["a東"]);
+    assert_eq!(Table::BTree, choose(Table::Auto, &alpha));
+
+    // cluster ranks are always dense
+    let (_, alpha) = grapheme_keys(&["a東😀"]);
+    assert_eq!(Table::Vec, choose(Table::Auto, &alpha));
+
+    assert_eq!(Table::Vec, choose(Table::Vec, &alpha));
+    assert_eq!(Table::BTree, choose(Table::BTree, &alpha));
+  }
+
+  #[test]
+  fn alphabet_test() {
+    let (keys, alpha) = code_point_keys(&["ba", "東"]);
+    assert_eq!(vec![vec![98, 97], vec![0x6771]], keys);
+    assert_eq!(97, alpha.min);
+    assert_eq!(0x6771, alpha.max);
+    assert_eq!(3, alpha.len);
+
+    let (keys, alpha) = grapheme_keys(&["👨‍👩‍👧a", "a"]);
+    assert_eq!(vec![vec![1, 0], vec![0]], keys);
+    assert_eq!(0, alpha.min);
+    assert_eq!(1, alpha.max);
+    assert_eq!(2, alpha.len);
+  }
+}
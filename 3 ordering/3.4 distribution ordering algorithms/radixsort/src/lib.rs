@@ -1,2 +1,3 @@
 mod countingsort;
 pub mod lexbucketsort;
+pub mod radix_key;
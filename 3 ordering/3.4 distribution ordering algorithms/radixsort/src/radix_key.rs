@@ -0,0 +1,338 @@
+use std::mem;
+
+/// Binary radix digit source
+/// # Summary
+/// Key is viewed as unsigned integer of `BITS` bits whose unsigned order
+/// matches key order, thus signed keys have sign bit flipped.
+pub trait RadixKey: Copy {
+    const BITS: u32;
+
+    /// Digit of `radix_bits` bits starting at bit `shift`.
+    fn digit(&self, shift: u32, radix_bits: u32) -> usize;
+}
+
+macro_rules! unsigned_radix_key {
+    ($($t:ty),*) => {
+        $(
+            impl RadixKey for $t {
+                const BITS: u32 = <$t>::BITS;
+
+                fn digit(&self, shift: u32, radix_bits: u32) -> usize {
+                    (*self >> shift) as usize & ((1 << radix_bits) - 1)
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! signed_radix_key {
+    ($($t:ty => $u:ty),*) => {
+        $(
+            impl RadixKey for $t {
+                const BITS: u32 = <$t>::BITS;
+
+                fn digit(&self, shift: u32, radix_bits: u32) -> usize {
+                    // flipping sign bit moves negatives below positives
+                    // while keeping two's complement order inside both halves
+                    let unsigned = *self as $u ^ (1 << (<$u>::BITS - 1));
+                    unsigned.digit(shift, radix_bits)
+                }
+            }
+        )*
+    };
+}
+
+unsigned_radix_key!(u8, u16, u32, u64, u128, usize);
+signed_radix_key!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize);
+
+/// Digit width of one counting pass
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Radix {
+    /// 256 counters
+    Bits8 = 8,
+    /// 2048 counters, fewer passes for keys of 32 and more bits
+    Bits11 = 11,
+}
+
+impl Radix {
+    /// `Bits8` for keys up to 16 bits, `Bits11` otherwise.
+    pub fn auto<K: RadixKey>() -> Radix {
+        if K::BITS <= 16 {
+            Radix::Bits8
+        } else {
+            Radix::Bits11
+        }
+    }
+}
+
+/// LSD binary radixsort of integer keys
+/// # Summary
+/// TC: ϴ(⌈BITS / radix⌉ × (n + 2ʳᵃᵈⁱˣ))
+/// <br/>SC: ϴ(n + 2ʳᵃᵈⁱˣ)
+pub fn sort<T: RadixKey>(items: &mut [T]) {
+    sort_with(items, Radix::auto::<T>())
+}
+
+pub fn sort_with<T: RadixKey>(items: &mut [T], radix: Radix) {
+    lsd(items, |x| *x, radix as u32);
+}
+
+/// Stable LSD binary radixsort of any items on integer key
+/// # Summary
+/// Keys are sorted along with item indexes, items are then permuted in place
+/// by swaps, so `T` need not be `Clone`.
+/// <br/>TC: ϴ(⌈BITS / radix⌉ × (n + 2ʳᵃᵈⁱˣ))
+/// <br/>SC: ϴ(n + 2ʳᵃᵈⁱˣ)
+pub fn sort_by_radix_key<T, K: RadixKey, F: Fn(&T) -> K>(items: &mut [T], key: F) {
+    sort_by_radix_key_with(items, Radix::auto::<K>(), key)
+}
+
+pub fn sort_by_radix_key_with<T, K: RadixKey, F: Fn(&T) -> K>(
+    items: &mut [T],
+    radix: Radix,
+    key: F,
+) {
+    if items.len() < 2 {
+        return;
+    }
+
+    let mut keyed = items
+        .iter()
+        .enumerate()
+        .map(|(ix, it)| (key(it), ix))
+        .collect::<Vec<(K, usize)>>();
+
+    lsd(&mut keyed, |k| k.0, radix as u32);
+
+    // `perm[ix]` — index of item that belongs to `ix`
+    let mut perm = keyed.into_iter().map(|k| k.1).collect::<Vec<usize>>();
+    permute(items, &mut perm);
+}
+
+fn lsd<T: Copy, K: RadixKey, F: Fn(&T) -> K>(items: &mut [T], key: F, radix_bits: u32) {
+    let items_len = items.len();
+    if items_len < 2 {
+        return;
+    }
+
+    let mut aux = items.to_vec();
+    let mut input: &mut [T] = items;
+    let mut output: &mut [T] = &mut aux;
+
+    // tracks whether sorted data are in `items` or `aux`
+    let mut in_items = true;
+
+    let mut counters = vec![0; 1 << radix_bits];
+
+    let mut shift = 0;
+    while shift < K::BITS {
+        counters.fill(0);
+
+        for it in input.iter() {
+            counters[key(it).digit(shift, radix_bits)] += 1;
+        }
+
+        // all items share digit, pass would keep order as is
+        if counters.contains(&items_len) {
+            shift += radix_bits;
+            continue;
+        }
+
+        // exclusive prefix sum specifies first index of each digit
+        let mut sum = 0;
+        for c in counters.iter_mut() {
+            let cnt = *c;
+            *c = sum;
+            sum += cnt;
+        }
+
+        // forward iteration keeps stability
+        for it in input.iter() {
+            let d = key(it).digit(shift, radix_bits);
+            output[counters[d]] = *it;
+            counters[d] += 1;
+        }
+
+        // spare writing one to another
+        // just swap them and continue
+        mem::swap(&mut input, &mut output);
+        in_items = !in_items;
+
+        shift += radix_bits;
+    }
+
+    if !in_items {
+        output.copy_from_slice(input);
+    }
+}
+
+// cycle-following permutation, each cycle of length c costs c - 1 swaps
+fn permute<T>(items: &mut [T], perm: &mut [usize]) {
+    for ix in 0..perm.len() {
+        let mut curr = ix;
+        while perm[curr] != ix {
+            let next = perm[curr];
+            items.swap(curr, next);
+            perm[curr] = curr;
+            curr = next;
+        }
+
+        perm[curr] = curr;
+    }
+}
+
+#[cfg(test)]
+mod tests_of_units {
+    use super::*;
+
+    // xorshift for determinism
+    fn pseudo_random(len: usize) -> Vec<u64> {
+        let mut x = 0x9E37_79B9_7F4A_7C15u64;
+        (0..len)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 7;
+                x ^= x << 17;
+                x
+            })
+            .collect()
+    }
+
+    macro_rules! type_test {
+        ($name:ident, $t:ty) => {
+            #[test]
+            fn $name() {
+                let mut nums = pseudo_random(1_000)
+                    .into_iter()
+                    .map(|x| x as $t)
+                    .chain([<$t>::MIN, <$t>::MAX, 0, 1, <$t>::MAX - 1])
+                    .collect::<Vec<$t>>();
+
+                let mut criterion = nums.clone();
+                criterion.sort();
+
+                let mut nums2 = nums.clone();
+
+                sort_with(&mut nums, Radix::Bits8);
+                assert_eq!(criterion, nums);
+
+                sort_with(&mut nums2, Radix::Bits11);
+                assert_eq!(criterion, nums2);
+            }
+        };
+    }
+
+    type_test!(u8_test, u8);
+    type_test!(u16_test, u16);
+    type_test!(u32_test, u32);
+    type_test!(u64_test, u64);
+    type_test!(u128_test, u128);
+    type_test!(usize_test, usize);
+    type_test!(i8_test, i8);
+    type_test!(i16_test, i16);
+    type_test!(i32_test, i32);
+    type_test!(i64_test, i64);
+    type_test!(i128_test, i128);
+    type_test!(isize_test, isize);
+
+    #[test]
+    fn signed_basic_test() {
+        let mut nums = [3, -1, 0, i32::MIN, -7, i32::MAX, 2];
+        sort(&mut nums);
+        assert_eq!([i32::MIN, -7, -1, 0, 2, 3, i32::MAX], nums);
+    }
+
+    #[test]
+    fn short_test() {
+        let mut nums: [u32; 0] = [];
+        sort(&mut nums);
+
+        let mut nums = [1u32];
+        sort(&mut nums);
+        assert_eq!([1], nums);
+    }
+
+    #[test]
+    fn digit_test() {
+        assert_eq!(0xCD, 0xABCDu16.digit(0, 8));
+        assert_eq!(0xAB, 0xABCDu16.digit(8, 8));
+        assert_eq!(0x7FF, u32::MAX.digit(11, 11));
+        // last pass takes remaining 10 bits only
+        assert_eq!(0x3FF, u32::MAX.digit(22, 11));
+
+        // sign bit flipped
+        assert_eq!(0x7F, (-1i8).digit(0, 8));
+        assert_eq!(0x80, 0i8.digit(0, 8));
+        assert_eq!(0x00, i8::MIN.digit(0, 8));
+    }
+
+    #[test]
+    fn radix_auto_test() {
+        assert_eq!(Radix::Bits8, Radix::auto::<u8>());
+        assert_eq!(Radix::Bits8, Radix::auto::<i16>());
+        assert_eq!(Radix::Bits11, Radix::auto::<u32>());
+        assert_eq!(Radix::Bits11, Radix::auto::<i128>());
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Task {
+        prio: i16,
+        name: String,
+    }
+
+    fn task(prio: i16, name: &str) -> Task {
+        Task {
+            prio,
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn sort_by_radix_key_test() {
+        // `Task` is not `Clone`
+        let mut tasks = [
+            task(3, "c"),
+            task(-1, "a"),
+            task(3, "c2"),
+            task(0, "b"),
+            task(-1, "a2"),
+            task(i16::MIN, "min"),
+        ];
+
+        sort_by_radix_key(&mut tasks, |t| t.prio);
+
+        let criterion = [
+            task(i16::MIN, "min"),
+            task(-1, "a"),
+            task(-1, "a2"),
+            task(0, "b"),
+            task(3, "c"),
+            task(3, "c2"),
+        ];
+        assert_eq!(criterion, tasks);
+    }
+
+    #[test]
+    fn sort_by_radix_key_stability_test() {
+        let mut pairs = pseudo_random(500)
+            .into_iter()
+            .enumerate()
+            .map(|(ix, x)| ((x % 7) as u8, ix))
+            .collect::<Vec<(u8, usize)>>();
+
+        let mut criterion = pairs.clone();
+        criterion.sort_by_key(|p| p.0);
+
+        sort_by_radix_key_with(&mut pairs, Radix::Bits11, |p| p.0);
+        assert_eq!(criterion, pairs);
+    }
+
+    #[test]
+    fn permute_test() {
+        let mut items = ['a', 'b', 'c', 'd', 'e'];
+        let mut perm = [2, 0, 1, 4, 3];
+
+        permute(&mut items, &mut perm);
+        assert_eq!(['c', 'a', 'b', 'e', 'd'], items);
+    }
+}
@@ -0,0 +1,76 @@
+/// Kasai LCP construction
+/// # Summary
+/// `lcp[ix]` is longest common prefix length of suffixes `sa[ix - 1]` and `sa[ix]`,
+/// `lcp[0] = 0`.
+/// <br/>Uses fact that moving from suffix `i` to `i + 1` shortens common prefix
+/// with its predecessor by 1 at most.
+/// <br/>TC: ϴ(n)
+/// <br/>SC: ϴ(n)
+pub fn kasai(text: &[u8], sa: &[usize]) -> Vec<usize> {
+    let n = text.len();
+    assert_eq!(n, sa.len(), "Suffix array must cover whole text.");
+
+    let mut rank = vec![0; n];
+    for (ix, s) in sa.iter().enumerate() {
+        rank[*s] = ix;
+    }
+
+    let mut lcp = vec![0; n];
+    let mut h = 0;
+    for i in 0..n {
+        let r = rank[i];
+        if r == 0 {
+            h = 0;
+            continue;
+        }
+
+        let j = sa[r - 1];
+        while i + h < n && j + h < n && text[i + h] == text[j + h] {
+            h += 1;
+        }
+
+        lcp[r] = h;
+        h = h.saturating_sub(1);
+    }
+
+    lcp
+}
+
+#[cfg(test)]
+mod tests_of_units {
+    use super::*;
+
+    #[test]
+    fn basic_test() {
+        let text = b"banana";
+        let sa = [5, 3, 1, 0, 4, 2];
+
+        assert_eq!(vec![0, 1, 3, 0, 0, 2], kasai(text, &sa));
+    }
+
+    #[test]
+    fn naive_test() {
+        let text = b"mississippi$mississippi";
+        let mut sa = (0..text.len()).collect::<Vec<usize>>();
+        sa.sort_by(|a, b| text[*a..].cmp(&text[*b..]));
+
+        let lcp = kasai(text, &sa);
+        assert_eq!(0, lcp[0]);
+        for ix in 1..sa.len() {
+            let (a, b) = (&text[sa[ix - 1]..], &text[sa[ix]..]);
+            let criterion = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+            assert_eq!(criterion, lcp[ix]);
+        }
+    }
+
+    #[test]
+    fn empty_test() {
+        assert_eq!(Vec::<usize>::new(), kasai(b"", &[]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Suffix array must cover whole text.")]
+    fn len_mismatch_test() {
+        kasai(b"ab", &[0]);
+    }
+}
@@ -0,0 +1,155 @@
+mod lcp;
+mod prefix_doubling;
+mod sais;
+
+use std::cmp::Ordering;
+
+/// Suffix array construction algorithm
+pub enum Construction {
+    /// ϴ(n × log n) rank pair doubling on `radixsort::lexbucketsort`
+    PrefixDoubling,
+    /// ϴ(n) induced sorting
+    SaIs,
+}
+
+/// Suffix array over byte text with optional LCP array
+/// # Summary
+/// Allows substring search in ϴ(m × log n) where m is pattern length.
+pub struct SuffixArray<'a> {
+    text: &'a [u8],
+    sa: Vec<usize>,
+    lcp: Option<Vec<usize>>,
+}
+
+impl<'a> SuffixArray<'a> {
+    pub fn new(text: &'a [u8], cons: Construction) -> SuffixArray<'a> {
+        let sa = match cons {
+            Construction::PrefixDoubling => prefix_doubling::build(text),
+            Construction::SaIs => sais::build(text),
+        };
+
+        SuffixArray {
+            text,
+            sa,
+            lcp: None,
+        }
+    }
+
+    pub fn text(&self) -> &'a [u8] {
+        self.text
+    }
+
+    pub fn sa(&self) -> &[usize] {
+        &self.sa
+    }
+
+    /// Computes LCP array on first call (Kasai), see `lcp()` for later access.
+    pub fn build_lcp(&mut self) -> &[usize] {
+        if self.lcp.is_none() {
+            self.lcp = Some(lcp::kasai(self.text, &self.sa));
+        }
+
+        self.lcp.as_ref().unwrap()
+    }
+
+    /// `None` until `build_lcp` is called.
+    pub fn lcp(&self) -> Option<&[usize]> {
+        self.lcp.as_deref()
+    }
+
+    /// Returns all text positions where `pattern` occurs, ascending.
+    /// <br/>Empty `pattern` matches every position.
+    pub fn find_all(&self, pattern: &[u8]) -> Vec<usize> {
+        let (start, end) = self.range(pattern);
+        let mut poss = self.sa[start..end].to_vec();
+        poss.sort_unstable();
+        poss
+    }
+
+    /// Suffix array range of suffixes prefixed by `pattern`.
+    fn range(&self, pattern: &[u8]) -> (usize, usize) {
+        let start = self
+            .sa
+            .partition_point(|s| self.cmp_prefix(*s, pattern) == Ordering::Less);
+        let end = start
+            + self.sa[start..].partition_point(|s| self.cmp_prefix(*s, pattern) == Ordering::Equal);
+
+        (start, end)
+    }
+
+    // compares suffix truncated to pattern length with pattern
+    fn cmp_prefix(&self, suffix: usize, pattern: &[u8]) -> Ordering {
+        let suffix = &self.text[suffix..];
+        let len = std::cmp::min(suffix.len(), pattern.len());
+        suffix[..len].cmp(pattern)
+    }
+}
+
+#[cfg(test)]
+mod tests_of_units {
+    use super::*;
+
+    const LOG: &[u8] = b"ERR disk full\nWARN disk slow\nERR disk full\nINFO ok\nERR net down\n";
+
+    fn naive_find_all(text: &[u8], pattern: &[u8]) -> Vec<usize> {
+        (0..text.len())
+            .filter(|i| text[*i..].starts_with(pattern))
+            .collect()
+    }
+
+    #[test]
+    fn constructions_match_test() {
+        let pd = SuffixArray::new(LOG, Construction::PrefixDoubling);
+        let sais = SuffixArray::new(LOG, Construction::SaIs);
+
+        assert_eq!(pd.sa(), sais.sa());
+    }
+
+    #[test]
+    fn find_all_test() {
+        for cons in [Construction::PrefixDoubling, Construction::SaIs] {
+            let sa = SuffixArray::new(LOG, cons);
+
+            assert_eq!(vec![0, 29, 51], sa.find_all(b"ERR"));
+            assert_eq!(vec![4, 19, 33], sa.find_all(b"disk"));
+            assert_eq!(vec![4, 33], sa.find_all(b"disk full"));
+            assert_eq!(Vec::<usize>::new(), sa.find_all(b"FATAL"));
+            assert_eq!(Vec::<usize>::new(), sa.find_all(b"down\nmore"));
+        }
+    }
+
+    #[test]
+    fn find_all_naive_test() {
+        let sa = SuffixArray::new(LOG, Construction::SaIs);
+        for pattern in [&b"d"[..], b"di", b"\n", b"ull\n", b"o", b"k\n"] {
+            assert_eq!(naive_find_all(LOG, pattern), sa.find_all(pattern));
+        }
+    }
+
+    #[test]
+    fn find_all_empty_pattern_test() {
+        let sa = SuffixArray::new(b"abc", Construction::SaIs);
+        assert_eq!(vec![0, 1, 2], sa.find_all(b""));
+    }
+
+    #[test]
+    fn find_all_empty_text_test() {
+        let sa = SuffixArray::new(b"", Construction::PrefixDoubling);
+        assert_eq!(Vec::<usize>::new(), sa.find_all(b"a"));
+    }
+
+    #[test]
+    fn lcp_test() {
+        let mut sa = SuffixArray::new(b"banana", Construction::SaIs);
+        assert_eq!(None, sa.lcp());
+
+        assert_eq!(&[0, 1, 3, 0, 0, 2], sa.build_lcp());
+        assert_eq!(Some(&[0, 1, 3, 0, 0, 2][..]), sa.lcp());
+    }
+
+    #[test]
+    fn text_test() {
+        let sa = SuffixArray::new(b"abc", Construction::SaIs);
+        assert_eq!(b"abc", sa.text());
+    }
+}
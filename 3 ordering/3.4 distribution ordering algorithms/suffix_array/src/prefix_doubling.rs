@@ -0,0 +1,120 @@
+use radixsort::lexbucketsort::{bucketsort, key::KeyProducer};
+
+/// Prefix-doubling (Manber–Myers) suffix sorting
+/// # Summary
+/// Each round sorts suffixes by rank pair `(rank[i], rank[i + k])` using
+/// two stable passes of `lexbucketsort::bucketsort`, then doubles `k`.
+/// <br/>TC: ϴ(n × log n × keys_cnt)
+/// <br/>SC: ϴ(n)
+pub fn build(text: &[u8]) -> Vec<usize> {
+    let n = text.len();
+    if n < 2 {
+        return (0..n).collect();
+    }
+
+    // 0 is reserved for "beyond text end"
+    let mut ranks = text.iter().map(|b| *b as usize + 1).collect::<Vec<usize>>();
+    let mut max_rank = 256;
+
+    let mut sa = vec![0; n];
+    let mut order = vec![0; n];
+    let mut vals = vec![0u64; n];
+
+    let mut k = 1;
+    loop {
+        let second = |i: usize| if i + k < n { ranks[i + k] } else { 0 };
+
+        // LSD pass on second rank
+        // index is encoded into value so it can be recovered after sort
+        for (i, v) in vals.iter_mut().enumerate() {
+            *v = (second(i) * n + i) as u64;
+        }
+        sort(&mut vals, (max_rank * n + n - 1) as u64);
+
+        for (o, v) in order.iter_mut().zip(vals.iter()) {
+            *o = *v as usize % n;
+        }
+
+        // MSD pass on first rank
+        // position in previous pass is encoded instead of index
+        // that way tie on first rank keeps second rank order
+        for (pos, v) in vals.iter_mut().enumerate() {
+            *v = (ranks[order[pos]] * n + pos) as u64;
+        }
+        sort(&mut vals, (max_rank * n + n - 1) as u64);
+
+        for (s, v) in sa.iter_mut().zip(vals.iter()) {
+            *s = order[*v as usize % n];
+        }
+
+        let mut new_ranks = vec![0; n];
+        let mut rank = 1;
+        new_ranks[sa[0]] = rank;
+        for ix in 1..n {
+            let (prev, curr) = (sa[ix - 1], sa[ix]);
+            if ranks[prev] != ranks[curr] || second(prev) != second(curr) {
+                rank += 1;
+            }
+            new_ranks[curr] = rank;
+        }
+
+        ranks = new_ranks;
+        max_rank = rank;
+
+        // all ranks unique ⇒ all suffixes ordered
+        if max_rank == n {
+            break;
+        }
+
+        k *= 2;
+    }
+
+    sa
+}
+
+fn sort(vals: &mut [u64], max: u64) {
+    let mut k_pducer = KeyProducer::new(max, vals.len());
+    let cycl_lim = k_pducer.keys_cnt();
+    bucketsort(vals, &mut k_pducer, cycl_lim);
+}
+
+#[cfg(test)]
+mod tests_of_units {
+    use super::*;
+
+    fn naive(text: &[u8]) -> Vec<usize> {
+        let mut sa = (0..text.len()).collect::<Vec<usize>>();
+        sa.sort_by(|a, b| text[*a..].cmp(&text[*b..]));
+        sa
+    }
+
+    #[test]
+    fn basic_test() {
+        let text = b"banana";
+        assert_eq!(vec![5, 3, 1, 0, 4, 2], build(text));
+    }
+
+    #[test]
+    fn empty_and_single_test() {
+        assert_eq!(Vec::<usize>::new(), build(b""));
+        assert_eq!(vec![0], build(b"x"));
+    }
+
+    #[test]
+    fn repetitive_test() {
+        let text = b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+        assert_eq!(naive(text), build(text));
+    }
+
+    #[test]
+    fn full_byte_range_test() {
+        let text = (0..=255u8).rev().chain(0..=255u8).collect::<Vec<u8>>();
+        assert_eq!(naive(&text), build(&text));
+    }
+
+    #[test]
+    fn log_like_test() {
+        let text = b"ERR disk full; WARN disk slow; ERR disk full; INFO ok; ERR net down";
+        assert_eq!(naive(text), build(text));
+    }
+}
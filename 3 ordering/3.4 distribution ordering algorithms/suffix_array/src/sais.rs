@@ -0,0 +1,232 @@
+// marks unfilled suffix array slot
+const EMPTY: usize = usize::MAX;
+
+/// SA-IS (Nong–Zhang–Chan) suffix sorting
+/// # Summary
+/// Sorts LMS substrings by induced sorting, recurses on their names
+/// when these are not unique and induces final order from sorted LMS suffixes.
+/// <br/>TC: ϴ(n + lenₐₗₚₕₐ)
+/// <br/>SC: ϴ(n + lenₐₗₚₕₐ)
+pub fn build(text: &[u8]) -> Vec<usize> {
+    let s = text.iter().map(|b| *b as usize).collect::<Vec<usize>>();
+    sais(&s, u8::MAX as usize)
+}
+
+/// `upper` — inclusive max symbol in `s`
+fn sais(s: &[usize], upper: usize) -> Vec<usize> {
+    let n = s.len();
+    match n {
+        0 => return vec![],
+        1 => return vec![0],
+        2 => return if s[0] < s[1] { vec![0, 1] } else { vec![1, 0] },
+        _ => {}
+    }
+
+    // S-type = true, L-type = false
+    // last suffix is L-type since virtual sentinel follows it
+    let mut ls = vec![false; n];
+    for i in (0..n - 1).rev() {
+        ls[i] = if s[i] == s[i + 1] {
+            ls[i + 1]
+        } else {
+            s[i] < s[i + 1]
+        };
+    }
+
+    // bucket boundaries
+    // `sum_l[c]` — start of c-bucket (its L-part)
+    // `sum_s[c]` — start of S-part of c-bucket
+    let mut sum_l = vec![0; upper + 1];
+    let mut sum_s = vec![0; upper + 1];
+    for i in 0..n {
+        if ls[i] {
+            sum_l[s[i] + 1] += 1;
+        } else {
+            sum_s[s[i]] += 1;
+        }
+    }
+
+    for c in 0..=upper {
+        sum_s[c] += sum_l[c];
+        if c < upper {
+            sum_l[c + 1] += sum_s[c];
+        }
+    }
+
+    let is_lms = |i: usize| i > 0 && !ls[i - 1] && ls[i];
+
+    let lms = (1..n).filter(|i| is_lms(*i)).collect::<Vec<usize>>();
+    let mut lms_map = vec![EMPTY; n];
+    for (ix, l) in lms.iter().enumerate() {
+        lms_map[*l] = ix;
+    }
+
+    let mut sa = vec![EMPTY; n];
+    let bounds = Bounds {
+        sum_l: &sum_l,
+        sum_s: &sum_s,
+    };
+    induce(&mut sa, s, &ls, &lms, &bounds);
+
+    let m = lms.len();
+    if m > 0 {
+        let mut sorted_lms = sa
+            .iter()
+            .copied()
+            .filter(|x| lms_map[*x] != EMPTY)
+            .collect::<Vec<usize>>();
+
+        // naming of LMS substrings
+        let mut rec_s = vec![0; m];
+        let mut rec_upper = 0;
+        for ix in 1..m {
+            let (mut l, mut r) = (sorted_lms[ix - 1], sorted_lms[ix]);
+            let end_l = if lms_map[l] + 1 < m {
+                lms[lms_map[l] + 1]
+            } else {
+                n
+            };
+            let end_r = if lms_map[r] + 1 < m {
+                lms[lms_map[r] + 1]
+            } else {
+                n
+            };
+
+            let mut same = end_l - l == end_r - r;
+            if same {
+                while l < end_l && s[l] == s[r] {
+                    l += 1;
+                    r += 1;
+                }
+
+                if l == n || r == n || s[l] != s[r] {
+                    same = false;
+                }
+            }
+
+            if !same {
+                rec_upper += 1;
+            }
+
+            rec_s[lms_map[sorted_lms[ix]]] = rec_upper;
+        }
+
+        let rec_sa = sais(&rec_s, rec_upper);
+        for (sl, rs) in sorted_lms.iter_mut().zip(rec_sa) {
+            *sl = lms[rs];
+        }
+
+        induce(&mut sa, s, &ls, &sorted_lms, &bounds);
+    }
+
+    sa
+}
+
+struct Bounds<'a> {
+    sum_l: &'a [usize],
+    sum_s: &'a [usize],
+}
+
+fn induce(sa: &mut [usize], s: &[usize], ls: &[bool], lms: &[usize], bounds: &Bounds) {
+    let n = s.len();
+    sa.fill(EMPTY);
+
+    // LMS suffixes into S-parts
+    let mut buf = bounds.sum_s.to_vec();
+    for d in lms {
+        let b = &mut buf[s[*d]];
+        sa[*b] = *d;
+        *b += 1;
+    }
+
+    // L-types from left
+    buf.copy_from_slice(bounds.sum_l);
+    let b = &mut buf[s[n - 1]];
+    sa[*b] = n - 1;
+    *b += 1;
+
+    for ix in 0..n {
+        let v = sa[ix];
+        if v != EMPTY && v > 0 && !ls[v - 1] {
+            let b = &mut buf[s[v - 1]];
+            sa[*b] = v - 1;
+            *b += 1;
+        }
+    }
+
+    // S-types from right
+    // S-bucket ends at start of next bucket
+    buf.copy_from_slice(bounds.sum_l);
+    for ix in (0..n).rev() {
+        let v = sa[ix];
+        if v != EMPTY && v > 0 && ls[v - 1] {
+            let b = &mut buf[s[v - 1] + 1];
+            *b -= 1;
+            sa[*b] = v - 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_of_units {
+    use super::*;
+
+    fn naive(text: &[u8]) -> Vec<usize> {
+        let mut sa = (0..text.len()).collect::<Vec<usize>>();
+        sa.sort_by(|a, b| text[*a..].cmp(&text[*b..]));
+        sa
+    }
+
+    #[test]
+    fn basic_test() {
+        let text = b"banana";
+        assert_eq!(vec![5, 3, 1, 0, 4, 2], build(text));
+    }
+
+    #[test]
+    fn empty_and_short_test() {
+        assert_eq!(Vec::<usize>::new(), build(b""));
+        assert_eq!(vec![0], build(b"x"));
+        assert_eq!(vec![1, 0], build(b"ba"));
+        assert_eq!(vec![1, 0], build(b"aa"));
+    }
+
+    #[test]
+    fn recursion_test() {
+        // repetitive LMS substrings force recursion
+        let text = b"mmiissiissiippii";
+        assert_eq!(naive(text), build(text));
+    }
+
+    #[test]
+    fn repetitive_test() {
+        let text = b"abababababababababababababababab";
+        assert_eq!(naive(text), build(text));
+    }
+
+    #[test]
+    fn full_byte_range_test() {
+        let text = (0..=255u8).rev().chain(0..=255u8).collect::<Vec<u8>>();
+        assert_eq!(naive(&text), build(&text));
+    }
+
+    #[test]
+    fn pseudo_random_test() {
+        // xorshift for determinism
+        let mut x = 0x2545_f491u32;
+        for len in [10, 100, 1_000] {
+            for alpha in [2, 4, 26] {
+                let text = (0..len)
+                    .map(|_| {
+                        x ^= x << 13;
+                        x ^= x >> 17;
+                        x ^= x << 5;
+                        b'a' + (x % alpha) as u8
+                    })
+                    .collect::<Vec<u8>>();
+
+                assert_eq!(naive(&text), build(&text));
+            }
+        }
+    }
+}
@@ -0,0 +1,269 @@
+use super::Iter;
+use std::mem::MaybeUninit;
+
+/// Heap backed ring buffer
+/// <br/>`enque` doubles capacity when full, `push_overwrite` keeps it.
+pub struct GrowableQueue<T> {
+    buf: Box<[MaybeUninit<T>]>,
+    rix: usize,
+    wix: usize,
+    count: usize,
+}
+
+impl<T> GrowableQueue<T> {
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    pub fn with_capacity(cap: usize) -> Self {
+        GrowableQueue {
+            buf: Box::new_uninit_slice(cap),
+            rix: 0,
+            wix: 0,
+            count: 0,
+        }
+    }
+
+    /// Always succeeds, `bool` is kept for `FixedSizeQueue` parity.
+    /// <br/>TC: Ο(1) amortized
+    pub fn enque(&mut self, t: T) -> bool {
+        self.try_enque(t).is_ok()
+    }
+
+    /// Always succeeds, `Result` is kept for `FixedSizeQueue` parity.
+    /// <br/>TC: Ο(1) amortized
+    pub fn try_enque(&mut self, t: T) -> Result<(), T> {
+        if self.is_full() {
+            self.grow();
+        }
+
+        let wix = self.wix;
+        self.buf[wix].write(t);
+
+        self.wix = (wix + 1) % self.buf.len();
+        self.count += 1;
+
+        Ok(())
+    }
+
+    pub fn deque(&mut self) -> Option<T> {
+        let count = self.count;
+        if count == 0 {
+            return None;
+        }
+
+        let rix = self.rix;
+        let t = unsafe { self.buf[rix].assume_init_read() };
+
+        self.rix = (rix + 1) % self.buf.len();
+        self.count = count - 1;
+
+        Some(t)
+    }
+
+    /// Enqueues `t` even when full, oldest item is dequeued then.
+    /// <br/>Returns dequeued item, or `t` itself for capacity 0.
+    pub fn push_overwrite(&mut self, t: T) -> Option<T> {
+        if self.buf.is_empty() {
+            return Some(t);
+        }
+
+        let oldest = if self.is_full() { self.deque() } else { None };
+
+        self.enque(t);
+        oldest
+    }
+
+    /// Oldest item.
+    pub fn peek(&self) -> Option<&T> {
+        if self.count == 0 {
+            return None;
+        }
+
+        Some(unsafe { self.buf[self.rix].assume_init_ref() })
+    }
+
+    /// Newest item.
+    pub fn peek_back(&self) -> Option<&T> {
+        if self.count == 0 {
+            return None;
+        }
+
+        let cap = self.buf.len();
+        let ix = (self.wix + cap - 1) % cap;
+        Some(unsafe { self.buf[ix].assume_init_ref() })
+    }
+
+    /// Items from oldest to newest.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            buf: &self.buf,
+            ix: self.rix,
+            left: self.count,
+        }
+    }
+
+    /// Items from oldest to newest.
+    /// <br/>Queue is empty afterwards even when `Drain` is not exhausted.
+    pub fn drain(&mut self) -> Drain<'_, T> {
+        Drain { queue: self }
+    }
+
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// `true` when next `enque` reallocates.
+    pub fn is_full(&self) -> bool {
+        self.count == self.buf.len()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.buf.len()
+    }
+
+    // items are moved to new buffer from its start
+    fn grow(&mut self) {
+        let cap = self.buf.len();
+        let new_cap = if cap == 0 { 1 } else { 2 * cap };
+
+        let mut buf = Box::new_uninit_slice(new_cap);
+
+        let count = self.count;
+        for slot in buf.iter_mut().take(count) {
+            // `deque` would decrement `count`
+            let rix = self.rix;
+            slot.write(unsafe { self.buf[rix].assume_init_read() });
+            self.rix = (rix + 1) % cap;
+        }
+
+        self.buf = buf;
+        self.rix = 0;
+        self.wix = count % new_cap;
+    }
+}
+
+impl<T> Default for GrowableQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for GrowableQueue<T> {
+    fn drop(&mut self) {
+        while self.deque().is_some() {}
+    }
+}
+
+pub struct Drain<'a, T> {
+    queue: &'a mut GrowableQueue<T>,
+}
+
+impl<T> Iterator for Drain<'_, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.queue.deque()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.queue.count, Some(self.queue.count))
+    }
+}
+
+impl<T> Drop for Drain<'_, T> {
+    fn drop(&mut self) {
+        self.for_each(drop);
+    }
+}
+
+#[cfg(test)]
+mod tests_of_units {
+    use super::GrowableQueue;
+    use std::rc::Rc;
+
+    #[test]
+    fn new() {
+        let queue = GrowableQueue::<u8>::new();
+
+        assert_eq!(0, queue.capacity());
+        assert!(queue.is_full());
+        assert!(queue.is_empty());
+        assert_eq!(None, queue.peek());
+    }
+
+    #[test]
+    fn enque_grows() {
+        let mut queue = GrowableQueue::new();
+
+        for n in 0..4 {
+            assert!(queue.enque(n));
+        }
+
+        assert_eq!(Ok(()), queue.try_enque(4));
+
+        assert_eq!(8, queue.capacity());
+        assert_eq!(5, queue.len());
+        assert_eq!(
+            vec![0, 1, 2, 3, 4],
+            queue.iter().copied().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn grow_wrapped() {
+        let mut queue = GrowableQueue::with_capacity(3);
+
+        for n in 0..3 {
+            _ = queue.enque(n);
+        }
+
+        _ = queue.deque();
+        _ = queue.enque(3);
+
+        // items wrap around buffer end
+        assert_eq!(1, queue.rix);
+        _ = queue.enque(4);
+
+        assert_eq!(6, queue.capacity());
+        assert_eq!(0, queue.rix);
+        assert_eq!(Some(&1), queue.peek());
+        assert_eq!(Some(&4), queue.peek_back());
+        assert_eq!(vec![1, 2, 3, 4], queue.drain().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn push_overwrite() {
+        let mut queue = GrowableQueue::with_capacity(2);
+
+        assert_eq!(None, queue.push_overwrite('a'));
+        assert_eq!(None, queue.push_overwrite('b'));
+        assert_eq!(Some('a'), queue.push_overwrite('c'));
+        assert_eq!(2, queue.capacity());
+
+        let mut queue = GrowableQueue::new();
+        assert_eq!(Some('a'), queue.push_overwrite('a'));
+    }
+
+    #[test]
+    fn drain_and_drop() {
+        let rc = Rc::new(());
+        let mut queue = GrowableQueue::new();
+
+        for _ in 0..5 {
+            _ = queue.enque(rc.clone());
+        }
+
+        _ = queue.drain().next();
+        assert!(queue.is_empty());
+        assert_eq!(1, Rc::strong_count(&rc));
+
+        _ = queue.enque(rc.clone());
+        drop(queue);
+        assert_eq!(1, Rc::strong_count(&rc));
+    }
+}
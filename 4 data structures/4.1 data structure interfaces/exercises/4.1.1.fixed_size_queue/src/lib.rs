@@ -1,36 +1,47 @@
+pub mod growable;
+pub mod spsc;
+
+use std::mem::MaybeUninit;
+
+/// Ring buffer of `CAP` slots
+/// <br/>Slots out of `count` items from `rix` on are uninitialized.
 pub struct FixedSizeQueue<T, const I: usize> {
-    arr: [T; I],
+    arr: [MaybeUninit<T>; I],
     rix: usize,
     wix: usize,
     count: usize,
 }
 
-impl<T, const CAP: usize> FixedSizeQueue<T, CAP>
-where
-    T: Default + Copy,
-{
+impl<T, const CAP: usize> FixedSizeQueue<T, CAP> {
     pub fn new() -> Self {
         FixedSizeQueue {
-            arr: [T::default(); CAP],
+            arr: [const { MaybeUninit::uninit() }; CAP],
             rix: 0,
             wix: 0,
             count: 0,
         }
     }
 
+    /// Returns `false` when full, `t` is dropped then.
+    /// <br/>Use `try_enque` to get `t` back.
     pub fn enque(&mut self, t: T) -> bool {
+        self.try_enque(t).is_ok()
+    }
+
+    /// Returns `Err(t)` when full.
+    pub fn try_enque(&mut self, t: T) -> Result<(), T> {
         let count = self.count;
         if count == CAP {
-            return false;
+            return Err(t);
         }
 
         let wix = self.wix;
-        self.arr[wix] = t;
+        self.arr[wix].write(t);
 
         self.wix = (wix + 1) % CAP;
         self.count = count + 1;
 
-        true
+        Ok(())
     }
 
     pub fn deque(&mut self) -> Option<T> {
@@ -40,13 +51,140 @@ where
         }
 
         let rix = self.rix;
-        let t = self.arr[rix];
+        let t = unsafe { self.arr[rix].assume_init_read() };
 
         self.rix = (rix + 1) % CAP;
         self.count = count - 1;
 
         Some(t)
     }
+
+    /// Enqueues `t` even when full, oldest item is dequeued then.
+    /// <br/>Returns dequeued item, or `t` itself for `CAP` 0.
+    pub fn push_overwrite(&mut self, t: T) -> Option<T> {
+        if CAP == 0 {
+            return Some(t);
+        }
+
+        let oldest = if self.count == CAP {
+            self.deque()
+        } else {
+            None
+        };
+
+        self.enque(t);
+        oldest
+    }
+
+    /// Oldest item.
+    pub fn peek(&self) -> Option<&T> {
+        if self.count == 0 {
+            return None;
+        }
+
+        Some(unsafe { self.arr[self.rix].assume_init_ref() })
+    }
+
+    /// Newest item.
+    pub fn peek_back(&self) -> Option<&T> {
+        if self.count == 0 {
+            return None;
+        }
+
+        let ix = (self.wix + CAP - 1) % CAP;
+        Some(unsafe { self.arr[ix].assume_init_ref() })
+    }
+
+    /// Items from oldest to newest.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            buf: &self.arr,
+            ix: self.rix,
+            left: self.count,
+        }
+    }
+
+    /// Items from oldest to newest.
+    /// <br/>Queue is empty afterwards even when `Drain` is not exhausted.
+    pub fn drain(&mut self) -> Drain<'_, T, CAP> {
+        Drain { queue: self }
+    }
+
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.count == CAP
+    }
+}
+
+impl<T, const CAP: usize> Default for FixedSizeQueue<T, CAP> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const CAP: usize> Drop for FixedSizeQueue<T, CAP> {
+    fn drop(&mut self) {
+        while self.deque().is_some() {}
+    }
+}
+
+/// Ring buffer items from oldest to newest
+pub struct Iter<'a, T> {
+    buf: &'a [MaybeUninit<T>],
+    ix: usize,
+    left: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        if self.left == 0 {
+            return None;
+        }
+
+        let ix = self.ix;
+        self.ix = (ix + 1) % self.buf.len();
+        self.left -= 1;
+
+        // only `left` items from `ix` on are visited
+        Some(unsafe { self.buf[ix].assume_init_ref() })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.left, Some(self.left))
+    }
+}
+
+impl<T> ExactSizeIterator for Iter<'_, T> {}
+
+pub struct Drain<'a, T, const CAP: usize> {
+    queue: &'a mut FixedSizeQueue<T, CAP>,
+}
+
+impl<T, const CAP: usize> Iterator for Drain<'_, T, CAP> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.queue.deque()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.queue.count, Some(self.queue.count))
+    }
+}
+
+impl<T, const CAP: usize> Drop for Drain<'_, T, CAP> {
+    fn drop(&mut self) {
+        self.for_each(drop);
+    }
 }
 
 #[cfg(test)]
@@ -57,7 +195,7 @@ mod tests_of_units {
     #[test]
     fn new() {
         let queue = FixedSizeQueue::<u8, 17>::new();
-        assert_eq!([0u8; 17], queue.arr);
+        assert_eq!(17, queue.arr.len());
         assert_eq!(0, queue.rix);
         assert_eq!(0, queue.wix);
         assert_eq!(0, queue.count);
@@ -73,7 +211,7 @@ mod tests_of_units {
         assert!(queue.enque(ENQ));
         assert_eq!(1, queue.wix);
         assert_eq!(1, queue.count);
-        assert_eq!(ENQ, queue.arr[0]);
+        assert_eq!(ENQ, unsafe { queue.arr[0].assume_init() });
     }
 
     #[test]
@@ -92,6 +230,16 @@ mod tests_of_units {
         assert!(queue.enque(1) == false);
     }
 
+    // try_enque()
+    #[test]
+    fn try_enque_test() {
+        let mut queue = FixedSizeQueue::<String, 1>::new();
+
+        assert_eq!(Ok(()), queue.try_enque("a".to_string()));
+        assert_eq!(Err("b".to_string()), queue.try_enque("b".to_string()));
+        assert_eq!(Some("a".to_string()), queue.deque());
+    }
+
     #[test]
     fn enque_wix_circulation_test() {
         let mut queue = FixedSizeQueue::<u8, 2>::new();
@@ -147,4 +295,91 @@ mod tests_of_units {
 
         assert_eq!(0, queue.rix);
     }
+
+    // push_overwrite()
+    #[test]
+    fn push_overwrite_test() {
+        let mut queue = FixedSizeQueue::<u8, 3>::new();
+
+        for n in 0..3 {
+            assert_eq!(None, queue.push_overwrite(n));
+        }
+
+        assert!(queue.is_full());
+        assert_eq!(Some(0), queue.push_overwrite(3));
+        assert_eq!(Some(1), queue.push_overwrite(4));
+
+        assert_eq!(vec![2, 3, 4], queue.iter().copied().collect::<Vec<_>>());
+
+        let mut queue = FixedSizeQueue::<u8, 0>::new();
+        assert_eq!(Some(1), queue.push_overwrite(1));
+    }
+
+    // peek(), peek_back()
+    #[test]
+    fn peek_test() {
+        let mut queue = FixedSizeQueue::<u8, 2>::new();
+        assert_eq!(None, queue.peek());
+        assert_eq!(None, queue.peek_back());
+
+        _ = queue.enque(1);
+        _ = queue.enque(2);
+        _ = queue.deque();
+        _ = queue.enque(3);
+
+        assert_eq!(1, queue.wix);
+        assert_eq!(Some(&2), queue.peek());
+        assert_eq!(Some(&3), queue.peek_back());
+    }
+
+    // iter(), len()
+    #[test]
+    fn iter_test() {
+        let mut queue = FixedSizeQueue::<String, 3>::new();
+
+        for s in ["a", "b", "c", "d"] {
+            _ = queue.push_overwrite(s.to_string());
+        }
+
+        let iter = queue.iter();
+        assert_eq!(3, iter.len());
+        assert_eq!("bcd", iter.map(|s| s.as_str()).collect::<String>());
+        assert_eq!(3, queue.len());
+    }
+
+    // drain()
+    #[test]
+    fn drain_test() {
+        let mut queue = FixedSizeQueue::<u8, 4>::new();
+        for n in 0..4 {
+            _ = queue.enque(n);
+        }
+
+        assert_eq!(vec![0, 1], queue.drain().take(2).collect::<Vec<_>>());
+        assert!(queue.is_empty());
+
+        _ = queue.enque(9);
+        assert_eq!(vec![9], queue.drain().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn drop_test() {
+        use std::rc::Rc;
+
+        let rc = Rc::new(());
+        let mut queue = FixedSizeQueue::<Rc<()>, 3>::new();
+
+        for _ in 0..5 {
+            _ = queue.push_overwrite(rc.clone());
+        }
+
+        assert_eq!(4, Rc::strong_count(&rc));
+
+        _ = queue.drain().next();
+        assert_eq!(1, Rc::strong_count(&rc));
+
+        _ = queue.enque(rc.clone());
+        drop(queue);
+        assert_eq!(1, Rc::strong_count(&rc));
+    }
 }
@@ -0,0 +1,370 @@
+//! Lock-free single producer, single consumer ring buffer
+//!
+//! `rix` and `wix` run modulo `2 * CAP`, slot index is their value modulo
+//! `CAP`. So full and empty buffer differ without item count.
+//! `wix` is written only by `Producer`, `rix` only by `Consumer`. Each side
+//! publishes its index with `Release` after slot access and reads the other
+//! one with `Acquire` before it.
+
+use std::cell::UnsafeCell;
+use std::mem::MaybeUninit;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+struct Ring<T, const CAP: usize> {
+    buf: [UnsafeCell<MaybeUninit<T>>; CAP],
+    rix: AtomicUsize,
+    wix: AtomicUsize,
+}
+
+// slot is accessed either by producer or by consumer, index handover synchronizes
+unsafe impl<T, const CAP: usize> Sync for Ring<T, CAP> where T: Send {}
+
+impl<T, const CAP: usize> Ring<T, CAP> {
+    // slot for index `ix`
+    fn slot(&self, ix: usize) -> *mut MaybeUninit<T> {
+        self.buf[ix % CAP].get()
+    }
+
+    fn advance(ix: usize, n: usize) -> usize {
+        (ix + n) % (2 * CAP)
+    }
+
+    fn distance(rix: usize, wix: usize) -> usize {
+        if CAP == 0 {
+            0
+        } else {
+            (wix + 2 * CAP - rix) % (2 * CAP)
+        }
+    }
+}
+
+impl<T, const CAP: usize> Drop for Ring<T, CAP> {
+    fn drop(&mut self) {
+        let rix = *self.rix.get_mut();
+        let wix = *self.wix.get_mut();
+
+        for ix in 0..Self::distance(rix, wix) {
+            unsafe { (*self.slot(rix + ix)).assume_init_drop() }
+        }
+    }
+}
+
+/// Writing handle
+pub struct Producer<T, const CAP: usize> {
+    ring: Arc<Ring<T, CAP>>,
+}
+
+/// Reading handle
+pub struct Consumer<T, const CAP: usize> {
+    ring: Arc<Ring<T, CAP>>,
+}
+
+/// Creates ring buffer of `CAP` slots.
+pub fn split<T, const CAP: usize>() -> (Producer<T, CAP>, Consumer<T, CAP>) {
+    let ring = Arc::new(Ring {
+        buf: [const { UnsafeCell::new(MaybeUninit::uninit()) }; CAP],
+        rix: AtomicUsize::new(0),
+        wix: AtomicUsize::new(0),
+    });
+
+    let producer = Producer { ring: ring.clone() };
+    (producer, Consumer { ring })
+}
+
+impl<T, const CAP: usize> Producer<T, CAP> {
+    /// Returns `Err(t)` when full.
+    pub fn push(&mut self, t: T) -> Result<(), T> {
+        let ring = &self.ring;
+
+        let wix = ring.wix.load(Ordering::Relaxed);
+        let rix = ring.rix.load(Ordering::Acquire);
+
+        if Ring::<T, CAP>::distance(rix, wix) == CAP {
+            return Err(t);
+        }
+
+        unsafe { (*ring.slot(wix)).write(t) };
+        ring.wix
+            .store(Ring::<T, CAP>::advance(wix, 1), Ordering::Release);
+
+        Ok(())
+    }
+
+    /// Pushes as many leading `items` as fit, returns their count.
+    pub fn push_slice(&mut self, items: &[T]) -> usize
+    where
+        T: Copy,
+    {
+        let ring = &self.ring;
+
+        let wix = ring.wix.load(Ordering::Relaxed);
+        let rix = ring.rix.load(Ordering::Acquire);
+
+        let free = CAP - Ring::<T, CAP>::distance(rix, wix);
+        let count = free.min(items.len());
+
+        if count == 0 {
+            return 0;
+        }
+
+        for (ix, t) in items[..count].iter().enumerate() {
+            unsafe { (*ring.slot(wix + ix)).write(*t) };
+        }
+
+        ring.wix
+            .store(Ring::<T, CAP>::advance(wix, count), Ordering::Release);
+        count
+    }
+
+    /// Item count at time of call.
+    pub fn len(&self) -> usize {
+        len(&self.ring)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len() == CAP
+    }
+}
+
+impl<T, const CAP: usize> Consumer<T, CAP> {
+    pub fn pop(&mut self) -> Option<T> {
+        let ring = &self.ring;
+
+        let rix = ring.rix.load(Ordering::Relaxed);
+        let wix = ring.wix.load(Ordering::Acquire);
+
+        if rix == wix {
+            return None;
+        }
+
+        let t = unsafe { (*ring.slot(rix)).assume_init_read() };
+        ring.rix
+            .store(Ring::<T, CAP>::advance(rix, 1), Ordering::Release);
+
+        Some(t)
+    }
+
+    /// Fills leading part of `out`, returns its length.
+    pub fn pop_slice(&mut self, out: &mut [T]) -> usize
+    where
+        T: Copy,
+    {
+        let ring = &self.ring;
+
+        let rix = ring.rix.load(Ordering::Relaxed);
+        let wix = ring.wix.load(Ordering::Acquire);
+
+        let count = Ring::<T, CAP>::distance(rix, wix).min(out.len());
+
+        if count == 0 {
+            return 0;
+        }
+
+        for (ix, t) in out[..count].iter_mut().enumerate() {
+            *t = unsafe { (*ring.slot(rix + ix)).assume_init_read() };
+        }
+
+        ring.rix
+            .store(Ring::<T, CAP>::advance(rix, count), Ordering::Release);
+        count
+    }
+
+    /// Item count at time of call.
+    pub fn len(&self) -> usize {
+        len(&self.ring)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len() == CAP
+    }
+}
+
+fn len<T, const CAP: usize>(ring: &Ring<T, CAP>) -> usize {
+    let rix = ring.rix.load(Ordering::Acquire);
+    let wix = ring.wix.load(Ordering::Acquire);
+
+    // `rix` read first, cannot outrun `wix`
+    Ring::<T, CAP>::distance(rix, wix)
+}
+
+#[cfg(test)]
+mod tests_of_units {
+    use super::split;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn basic_test() {
+        let (mut prod, mut cons) = split::<String, 2>();
+
+        assert_eq!(None, cons.pop());
+        assert_eq!(Ok(()), prod.push("a".to_string()));
+        assert_eq!(Ok(()), prod.push("b".to_string()));
+        assert_eq!(Err("c".to_string()), prod.push("c".to_string()));
+
+        assert!(prod.is_full());
+        assert_eq!(Some("a".to_string()), cons.pop());
+        assert_eq!(1, cons.len());
+
+        assert_eq!(Ok(()), prod.push("c".to_string()));
+        assert_eq!(Some("b".to_string()), cons.pop());
+        assert_eq!(Some("c".to_string()), cons.pop());
+        assert!(cons.is_empty());
+    }
+
+    #[test]
+    fn zero_capacity() {
+        let (mut prod, mut cons) = split::<u8, 0>();
+
+        assert_eq!(Err(1), prod.push(1));
+        assert_eq!(0, prod.push_slice(&[1, 2]));
+        assert_eq!(None, cons.pop());
+    }
+
+    #[test]
+    fn slices() {
+        let (mut prod, mut cons) = split::<u8, 4>();
+
+        assert_eq!(3, prod.push_slice(&[0, 1, 2]));
+        assert_eq!(1, prod.push_slice(&[3, 4, 5]));
+
+        let mut out = [0; 3];
+        assert_eq!(3, cons.pop_slice(&mut out));
+        assert_eq!([0, 1, 2], out);
+
+        // wraps around buffer end
+        assert_eq!(2, prod.push_slice(&[4, 5]));
+        assert_eq!(3, cons.pop_slice(&mut out));
+        assert_eq!([3, 4, 5], out);
+        assert_eq!(0, cons.pop_slice(&mut out));
+    }
+
+    #[test]
+    fn index_wrapping() {
+        let (mut prod, mut cons) = split::<u8, 3>();
+
+        for round in 0..4 {
+            for n in 0..3 {
+                assert_eq!(Ok(()), prod.push(n));
+            }
+
+            assert!(prod.is_full());
+            assert_eq!(Err(3), prod.push(3));
+
+            for n in 0..3 {
+                assert_eq!(Some(n), cons.pop());
+            }
+
+            assert!(cons.is_empty());
+            assert_eq!((round * 3 + 3) % 6, prod.ring.wix.load(Ordering::Relaxed));
+        }
+    }
+
+    struct Counted(Arc<AtomicUsize>);
+
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[test]
+    fn drop_left_items() {
+        let drops = Arc::new(AtomicUsize::new(0));
+        let (mut prod, mut cons) = split::<Counted, 4>();
+
+        for _ in 0..4 {
+            _ = prod.push(Counted(drops.clone()));
+        }
+
+        drop(cons.pop());
+        drop(prod);
+        assert_eq!(1, drops.load(Ordering::Relaxed));
+
+        drop(cons);
+        assert_eq!(4, drops.load(Ordering::Relaxed));
+    }
+
+    const STRESS: usize = 200_000;
+
+    #[test]
+    fn stress_items() {
+        let (mut prod, mut cons) = split::<Box<usize>, 7>();
+
+        let producer = thread::spawn(move || {
+            for n in 0..STRESS {
+                let mut t = Box::new(n);
+
+                while let Err(back) = prod.push(t) {
+                    t = back;
+                    thread::yield_now();
+                }
+            }
+        });
+
+        let mut expected = 0;
+        while expected < STRESS {
+            match cons.pop() {
+                Some(n) => {
+                    assert_eq!(expected, *n);
+                    expected += 1;
+                }
+                None => thread::yield_now(),
+            }
+        }
+
+        producer.join().unwrap();
+        assert!(cons.is_empty());
+    }
+
+    #[test]
+    fn stress_slices() {
+        let (mut prod, mut cons) = split::<usize, 64>();
+
+        let producer = thread::spawn(move || {
+            let items = (0..STRESS).collect::<Vec<_>>();
+
+            let mut ix = 0;
+            let mut batch = 1;
+            while ix < STRESS {
+                let end = (ix + batch).min(STRESS);
+                let count = prod.push_slice(&items[ix..end]);
+
+                if count == 0 {
+                    thread::yield_now();
+                }
+
+                ix += count;
+
+                batch = batch % 97 + 1;
+            }
+        });
+
+        let mut out = [0; 41];
+        let mut expected = 0;
+        while expected < STRESS {
+            let count = cons.pop_slice(&mut out[..expected % 41 + 1]);
+
+            if count == 0 {
+                thread::yield_now();
+            }
+
+            for n in &out[..count] {
+                assert_eq!(expected, *n);
+                expected += 1;
+            }
+        }
+
+        producer.join().unwrap();
+    }
+}
@@ -1,3 +1,3 @@
 #![allow(dead_code)]
 mod plain;
-mod reuse;
+pub mod reuse;
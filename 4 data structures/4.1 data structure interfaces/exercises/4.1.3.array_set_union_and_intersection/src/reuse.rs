@@ -1,101 +1,285 @@
 #![allow(non_snake_case)]
 
+use std::iter::{self, Copied, Peekable};
+use std::slice::Iter;
 use std::vec::Vec;
 
 pub fn union<T>(A: &[T], B: &[T]) -> Vec<T>
 where
     T: PartialOrd + Copy,
 {
-    let result = nucleus(A, B, MergeType::Union);
+    union_iter(A, B).collect()
+}
 
-    let mut i = result.i;
-    let mut j = result.j;
+pub fn intersection<T>(A: &[T], B: &[T]) -> Vec<T>
+where
+    T: PartialOrd + Copy,
+{
+    intersection_iter(A, B).collect()
+}
 
-    let A_len = result.A_len;
-    let B_len = result.B_len;
+/// items of `A` not in `B`
+pub fn difference<T>(A: &[T], B: &[T]) -> Vec<T>
+where
+    T: PartialOrd + Copy,
+{
+    difference_iter(A, B).collect()
+}
 
-    let mut output = result.output;
+/// items in exactly one of `A`, `B`
+pub fn symmetric_difference<T>(A: &[T], B: &[T]) -> Vec<T>
+where
+    T: PartialOrd + Copy,
+{
+    symmetric_difference_iter(A, B).collect()
+}
 
-    while i < A_len {
-        output.push(A[i]);
-        i += 1;
-    }
+/// `A ⊆ B`
+pub fn is_subset<T>(A: &[T], B: &[T]) -> bool
+where
+    T: PartialOrd + Copy,
+{
+    difference_iter(A, B).next().is_none()
+}
 
-    while j < B_len {
-        output.push(B[j]);
-        j += 1;
-    }
+pub fn is_disjoint<T>(A: &[T], B: &[T]) -> bool
+where
+    T: PartialOrd + Copy,
+{
+    intersection_iter(A, B).next().is_none()
+}
 
-    output
+pub fn union_iter<'a, T>(A: &'a [T], B: &'a [T]) -> SetIter<SliceIter<'a, T>, SliceIter<'a, T>>
+where
+    T: PartialOrd + Copy,
+{
+    SetIter::new(slice(A), slice(B), MergeType::Union)
 }
 
-pub fn intersection<T>(A: &[T], B: &[T]) -> Vec<T>
+pub fn intersection_iter<'a, T>(
+    A: &'a [T],
+    B: &'a [T],
+) -> SetIter<SliceIter<'a, T>, SliceIter<'a, T>>
+where
+    T: PartialOrd + Copy,
+{
+    SetIter::new(slice(A), slice(B), MergeType::Intersection)
+}
+
+pub fn difference_iter<'a, T>(A: &'a [T], B: &'a [T]) -> SetIter<SliceIter<'a, T>, SliceIter<'a, T>>
+where
+    T: PartialOrd + Copy,
+{
+    SetIter::new(slice(A), slice(B), MergeType::Difference)
+}
+
+pub fn symmetric_difference_iter<'a, T>(
+    A: &'a [T],
+    B: &'a [T],
+) -> SetIter<SliceIter<'a, T>, SliceIter<'a, T>>
 where
     T: PartialOrd + Copy,
 {
-    nucleus(A, B, MergeType::Intersection).output
+    SetIter::new(slice(A), slice(B), MergeType::SymmetricDifference)
 }
 
-#[derive(PartialEq)]
+pub fn union_k<T>(sets: &[&[T]]) -> Vec<T>
+where
+    T: PartialOrd + Copy,
+{
+    union_k_iter(sets).collect()
+}
+
+/// intersection of no set is empty
+pub fn intersection_k<T>(sets: &[&[T]]) -> Vec<T>
+where
+    T: PartialOrd + Copy,
+{
+    intersection_k_iter(sets).collect()
+}
+
+/// balanced tree of pairwise merges
+/// <br/>TC: Ο(N log k), N is sum of set lengths
+pub fn union_k_iter<'a, T>(sets: &[&'a [T]]) -> KWayIter<'a, T>
+where
+    T: PartialOrd + Copy,
+{
+    k_way(sets, MergeType::Union)
+}
+
+/// balanced tree of pairwise merges
+/// <br/>TC: Ο(N log k), N is sum of set lengths
+pub fn intersection_k_iter<'a, T>(sets: &[&'a [T]]) -> KWayIter<'a, T>
+where
+    T: PartialOrd + Copy,
+{
+    k_way(sets, MergeType::Intersection)
+}
+
+pub type SliceIter<'a, T> = Copied<Iter<'a, T>>;
+
+pub type KWayIter<'a, T> = Box<dyn Iterator<Item = T> + 'a>;
+
+fn slice<T>(A: &[T]) -> SliceIter<'_, T>
+where
+    T: Copy,
+{
+    A.iter().copied()
+}
+
+fn k_way<'a, T>(sets: &[&'a [T]], mt: MergeType) -> KWayIter<'a, T>
+where
+    T: PartialOrd + Copy,
+{
+    match sets.len() {
+        0 => Box::new(iter::empty()),
+        1 => Box::new(slice(sets[0])),
+        len => {
+            let (l, r) = sets.split_at(len / 2);
+            Box::new(SetIter::new(k_way(l, mt), k_way(r, mt), mt))
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Copy)]
 enum MergeType {
     Intersection,
     Union,
+    Difference,
+    SymmetricDifference,
 }
 
-struct MergeResult<T> {
-    i: usize,
-    j: usize,
-    A_len: usize,
-    B_len: usize,
-    output: Vec<T>,
+impl MergeType {
+    fn keeps<T>(&self, step: &Step<T>) -> bool {
+        match step {
+            Step::A(_) => *self != MergeType::Intersection,
+            Step::B(_) => *self == MergeType::Union || *self == MergeType::SymmetricDifference,
+            Step::Both(_) => *self == MergeType::Union || *self == MergeType::Intersection,
+        }
+    }
 }
 
-fn nucleus<T>(A: &[T], B: &[T], mt: MergeType) -> MergeResult<T>
+/// lazy set operation over ordered sets
+pub struct SetIter<I, J>
 where
-    T: PartialOrd + Copy,
+    I: Iterator,
+    J: Iterator<Item = I::Item>,
 {
-    let mut i = 0;
-    let mut j = 0;
+    nucleus: Nucleus<I, J>,
+    mt: MergeType,
+}
+
+impl<I, J> SetIter<I, J>
+where
+    I: Iterator,
+    J: Iterator<Item = I::Item>,
+    I::Item: PartialOrd + Copy,
+{
+    fn new(A: I, B: J, mt: MergeType) -> Self {
+        SetIter {
+            nucleus: nucleus(A, B),
+            mt,
+        }
+    }
+
+    // no kept step can come anymore
+    fn exhausted(&mut self) -> bool {
+        let nucleus = &mut self.nucleus;
 
-    let A_len = A.len();
-    let B_len = B.len();
+        match self.mt {
+            MergeType::Intersection => nucleus.A.peek().is_none() || nucleus.B.peek().is_none(),
+            MergeType::Difference => nucleus.A.peek().is_none(),
+            _ => false,
+        }
+    }
+}
 
-    let mut output = Vec::new();
+impl<I, J> Iterator for SetIter<I, J>
+where
+    I: Iterator,
+    J: Iterator<Item = I::Item>,
+    I::Item: PartialOrd + Copy,
+{
+    type Item = I::Item;
 
-    let doUnion = mt == MergeType::Union;
+    fn next(&mut self) -> Option<I::Item> {
+        while !self.exhausted() {
+            let step = self.nucleus.next()?;
 
-    // TC: Ο(m+n)
-    // TC: Ω(m or n)
-    while j < B_len && i < A_len {
-        if A[i] < B[j] {
-            if doUnion {
-                output.push(A[i]);
+            if self.mt.keeps(&step) {
+                return Some(step.item());
             }
-
-            i += 1;
-            continue;
         }
 
-        if A[i] > B[j] {
-            if doUnion {
-                output.push(B[j]);
-            }
+        None
+    }
+}
+
+// origin of merged item
+enum Step<T> {
+    A(T),
+    B(T),
+    Both(T),
+}
 
-            j += 1;
-            continue;
+impl<T> Step<T> {
+    fn item(self) -> T {
+        match self {
+            Step::A(t) | Step::B(t) | Step::Both(t) => t,
         }
+    }
+}
 
-        output.push(A[i]);
-        i += 1;
-        j += 1;
+struct Nucleus<I, J>
+where
+    I: Iterator,
+    J: Iterator<Item = I::Item>,
+{
+    A: Peekable<I>,
+    B: Peekable<J>,
+}
+
+fn nucleus<I, J>(A: I, B: J) -> Nucleus<I, J>
+where
+    I: Iterator,
+    J: Iterator<Item = I::Item>,
+{
+    Nucleus {
+        A: A.peekable(),
+        B: B.peekable(),
     }
+}
+
+// single pass merge, tails included
+// TC: Ο(m+n)
+impl<I, J> Iterator for Nucleus<I, J>
+where
+    I: Iterator,
+    J: Iterator<Item = I::Item>,
+    I::Item: PartialOrd + Copy,
+{
+    type Item = Step<I::Item>;
+
+    fn next(&mut self) -> Option<Step<I::Item>> {
+        let (a, b) = match (self.A.peek(), self.B.peek()) {
+            (Some(a), Some(b)) => (*a, *b),
+            (Some(_), None) => return self.A.next().map(Step::A),
+            (None, _) => return self.B.next().map(Step::B),
+        };
+
+        if a < b {
+            self.A.next();
+            return Some(Step::A(a));
+        }
+
+        if a > b {
+            self.B.next();
+            return Some(Step::B(b));
+        }
 
-    MergeResult {
-        i,
-        j,
-        A_len,
-        B_len,
-        output,
+        self.A.next();
+        self.B.next();
+        Some(Step::Both(a))
     }
 }
 
@@ -181,4 +365,120 @@ mod tests_of_units {
             assert_eq!(&[1, 2, 3, 4, 9], test.as_slice());
         }
     }
+
+    mod difference_tests {
+        use super::super::{difference, symmetric_difference};
+
+        #[test]
+        fn basic_test() {
+            let A = [1, 3, 5, 6, 7];
+            let B = [2, 3, 4, 6];
+
+            assert_eq!(&[1, 5, 7], difference(&A, &B).as_slice());
+            assert_eq!(&[2, 4], difference(&B, &A).as_slice());
+        }
+
+        #[test]
+        fn symmetric_test() {
+            let A = [1, 3, 5, 6, 7];
+            let B = [2, 3, 4, 6];
+
+            let test = symmetric_difference(&A, &B);
+            assert_eq!(&[1, 2, 4, 5, 7], test.as_slice());
+            assert_eq!(test, symmetric_difference(&B, &A));
+        }
+
+        #[test]
+        fn empty_B() {
+            let A = [1, 2];
+
+            assert_eq!(&[1, 2], difference(&A, &[]).as_slice());
+            assert_eq!(&[0; 0], difference(&[], &A).as_slice());
+            assert_eq!(&[1, 2], symmetric_difference(&[], &A).as_slice());
+        }
+    }
+
+    mod predicate_tests {
+        use super::super::{is_disjoint, is_subset};
+
+        #[test]
+        fn subset_test() {
+            let A = [2, 6];
+            let B = [1, 2, 3, 6, 7];
+
+            assert!(is_subset(&A, &B));
+            assert!(!is_subset(&B, &A));
+            assert!(!is_subset(&[2, 4], &B));
+            assert!(is_subset(&[], &A));
+            assert!(is_subset(&A, &A));
+        }
+
+        #[test]
+        fn disjoint_test() {
+            let A = [1, 3, 5];
+
+            assert!(is_disjoint(&A, &[2, 4, 6]));
+            assert!(!is_disjoint(&A, &[4, 5]));
+            assert!(is_disjoint(&A, &[]));
+        }
+    }
+
+    mod k_way_tests {
+        use super::super::{intersection_k, union_k};
+
+        #[test]
+        fn union_test() {
+            let sets: [&[u8]; 4] = [&[1, 4, 9], &[2, 4], &[], &[0, 9, 10]];
+
+            let test = union_k(&sets);
+            assert_eq!(&[0, 1, 2, 4, 9, 10], test.as_slice());
+        }
+
+        #[test]
+        fn intersection_test() {
+            let sets: [&[u8]; 3] = [&[1, 3, 4, 9], &[3, 4, 8, 9], &[0, 3, 9]];
+
+            let test = intersection_k(&sets);
+            assert_eq!(&[3, 9], test.as_slice());
+        }
+
+        #[test]
+        fn few_sets() {
+            assert_eq!(&[0u8; 0], union_k(&[]).as_slice());
+            assert_eq!(&[0u8; 0], intersection_k(&[]).as_slice());
+            assert_eq!(&[1, 2], intersection_k(&[&[1, 2]]).as_slice());
+        }
+    }
+
+    mod lazy_tests {
+        use super::super::{intersection_iter, union_iter, MergeType, SetIter};
+        use std::cell::Cell;
+
+        #[test]
+        fn stepwise() {
+            let A = [1, 3, 5];
+            let B = [2, 3];
+
+            let mut test = union_iter(&A, &B);
+            assert_eq!(Some(1), test.next());
+            assert_eq!(Some(2), test.next());
+            assert_eq!(Some(3), test.next());
+            assert_eq!(Some(5), test.next());
+            assert_eq!(None, test.next());
+        }
+
+        #[test]
+        fn stops_on_exhausted_side() {
+            let pulled = Cell::new(0);
+            let B = (2..1000).inspect(|_| pulled.set(pulled.get() + 1));
+
+            let test = SetIter::new([1, 2].into_iter(), B, MergeType::Intersection);
+            assert_eq!(vec![2], test.collect::<Vec<_>>());
+            // only 2 pulled, 3 and on are not
+            assert_eq!(1, pulled.get());
+
+            let mut test = intersection_iter(&[7], &[1, 2]);
+            assert_eq!(None, test.next());
+        }
+    }
 }
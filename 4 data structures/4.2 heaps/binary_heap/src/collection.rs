@@ -0,0 +1,265 @@
+//! `std::collections::BinaryHeap` like API
+//!
+//! - `iter` — items in arbitrary order
+//! - `drain` — arbitrary order, `drain_sorted` — root first
+//! - `into_sorted_vec` — root last, as for `Max` ordered `BinaryHeap`
+//! - `retain` — re-heapifies kept items
+//! - `Extend`, `FromIterator`
+//!
+//! Aliases in `compat` inherit all of it, `FromIterator` for their default form.
+
+use super::{Heap, KeyFn, Order, Storage};
+use std::iter::FromIterator;
+
+impl<T, O, K, S> Heap<T, O, K, S>
+where
+    O: Order,
+    K: KeyFn<T>,
+    S: Storage<T>,
+{
+    /// Items in level order.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.as_slice().iter()
+    }
+
+    /// Heap is empty afterwards even when `Drain` is not exhausted.
+    pub fn drain(&mut self) -> Drain<'_, T, O, K, S> {
+        Drain { heap: self }
+    }
+
+    /// Heap is empty afterwards even when `DrainSorted` is not exhausted.
+    pub fn drain_sorted(&mut self) -> DrainSorted<'_, T, O, K, S> {
+        DrainSorted { heap: self }
+    }
+
+    /// TC: Ο(n log n)
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut sorted = Vec::with_capacity(self.len());
+
+        while let Some(t) = self.extract_root() {
+            sorted.push(t);
+        }
+
+        sorted.reverse();
+        sorted
+    }
+
+    /// Keeps only items `pred` approves.
+    /// <br/>TC: Ο(n)
+    pub fn retain<F>(&mut self, mut pred: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let len = self.len();
+        let items = self.store.items_mut();
+
+        let mut wr_ix = 0;
+        for rd_ix in 0..len {
+            if pred(&items[rd_ix]) {
+                items.swap(wr_ix, rd_ix);
+                wr_ix += 1;
+            }
+        }
+
+        for _ in wr_ix..len {
+            self.store.pop();
+        }
+
+        self.heapify();
+    }
+}
+
+/// Panics when `Fixed` storage capacity is exceeded.
+impl<T, O, K, S> Extend<T> for Heap<T, O, K, S>
+where
+    O: Order,
+    K: KeyFn<T>,
+    S: Storage<T>,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for t in iter {
+            if self.insert(t).is_err() {
+                panic!("Heap capacity exceeded.");
+            }
+        }
+    }
+}
+
+/// Heap uses default policies and capacity of collected `Vec<T>`.
+impl<T, O, K, S> FromIterator<T> for Heap<T, O, K, S>
+where
+    O: Order + Default,
+    K: KeyFn<T> + Default,
+    S: Storage<T>,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+    {
+        Heap::from_vec_with(iter.into_iter().collect(), O::default(), K::default())
+    }
+}
+
+pub struct Drain<'a, T, O, K, S>
+where
+    O: Order,
+    K: KeyFn<T>,
+    S: Storage<T>,
+{
+    heap: &'a mut Heap<T, O, K, S>,
+}
+
+impl<T, O, K, S> Iterator for Drain<'_, T, O, K, S>
+where
+    O: Order,
+    K: KeyFn<T>,
+    S: Storage<T>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.heap.store.pop()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.heap.len(), Some(self.heap.len()))
+    }
+}
+
+impl<T, O, K, S> Drop for Drain<'_, T, O, K, S>
+where
+    O: Order,
+    K: KeyFn<T>,
+    S: Storage<T>,
+{
+    fn drop(&mut self) {
+        self.for_each(drop);
+    }
+}
+
+pub struct DrainSorted<'a, T, O, K, S>
+where
+    O: Order,
+    K: KeyFn<T>,
+    S: Storage<T>,
+{
+    heap: &'a mut Heap<T, O, K, S>,
+}
+
+impl<T, O, K, S> Iterator for DrainSorted<'_, T, O, K, S>
+where
+    O: Order,
+    K: KeyFn<T>,
+    S: Storage<T>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.heap.extract_root()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.heap.len(), Some(self.heap.len()))
+    }
+}
+
+impl<T, O, K, S> Drop for DrainSorted<'_, T, O, K, S>
+where
+    O: Order,
+    K: KeyFn<T>,
+    S: Storage<T>,
+{
+    fn drop(&mut self) {
+        self.heap.drain();
+    }
+}
+
+#[cfg(test)]
+mod tests_of_units {
+    use crate::{Form, Growable, Heap, Identity, Max, MinHeap};
+
+    #[test]
+    fn iter_drain() {
+        let mut heap = MinHeap::new(2);
+        heap.extend([4, 1, 3]);
+
+        let mut items = heap.iter().copied().collect::<Vec<_>>();
+        items.sort();
+        assert_eq!(vec![1, 3, 4], items);
+
+        let mut items = heap.drain().collect::<Vec<_>>();
+        items.sort();
+        assert_eq!(vec![1, 3, 4], items);
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn drain_sorted() {
+        let mut heap = (0..6).collect::<Heap<u8, Max>>();
+
+        assert_eq!(vec![5, 4], heap.drain_sorted().take(2).collect::<Vec<_>>());
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn into_sorted_vec() {
+        let heap = [3, 9, 1, 7].into_iter().collect::<Heap<u8, Form>>();
+        assert_eq!(vec![1, 3, 7, 9], heap.into_sorted_vec());
+
+        let heap = [3, 9, 1, 7].into_iter().collect::<MinHeap<u8>>();
+        assert_eq!(vec![9, 7, 3, 1], heap.into_sorted_vec());
+    }
+
+    #[test]
+    fn retain() {
+        let mut heap = Heap::<u8, Max, Identity, Growable<u8>>::with_policies(0, Max, Identity);
+        heap.extend(0..10);
+
+        heap.retain(|n| n % 3 != 0);
+        assert_eq!(6, heap.len());
+        assert_eq!(vec![1, 2, 4, 5, 7, 8], heap.into_sorted_vec());
+    }
+
+    #[test]
+    fn compat_aliases() {
+        use crate::compat::{delete_operation, fixed_binary_heap, fixed_minimum_binary_heap};
+        use crate::compat::{keyed_fixed_binary_heap, keyed_fixed_binary_heap::FixBinHeapForm};
+
+        let heap = [3, 9, 1]
+            .into_iter()
+            .collect::<fixed_binary_heap::FixBinHeap<u8>>();
+        assert_eq!(vec![1, 3, 9], heap.into_sorted_vec());
+
+        let mut heap = fixed_binary_heap::growable::GrowBinHeap::new(|a: &u8, b: &u8| b.cmp(a));
+        heap.extend([3, 9, 1]);
+        assert_eq!(vec![1, 3], heap.drain_sorted().take(2).collect::<Vec<_>>());
+
+        let mut heap = [3, 9, 1, 6]
+            .into_iter()
+            .collect::<fixed_minimum_binary_heap::FixMinBinHeap<u8>>();
+        heap.retain(|n| n % 3 == 0);
+        assert_eq!(vec![3, 6, 9], heap.drain_sorted().collect::<Vec<_>>());
+
+        let mut heap = keyed_fixed_binary_heap::FixBinHeap::<u16>::new(2, FixBinHeapForm::Maximal);
+        heap.extend([3, 9, 1]);
+        assert_eq!(3, heap.iter().count());
+        assert_eq!(vec![9, 3, 1], heap.into_sorted_vec());
+
+        let mut heap = [3, 9, 1]
+            .into_iter()
+            .collect::<delete_operation::FixBinHeap<u8>>();
+        assert_eq!(Ok(()), heap.del(0));
+        heap.extend([7]);
+        assert_eq!(vec![1, 3, 7], heap.into_sorted_vec());
+    }
+
+    #[test]
+    #[should_panic(expected = "Heap capacity exceeded.")]
+    fn extend_overflow() {
+        let mut heap = MinHeap::new(0);
+        heap.extend([1, 2]);
+    }
+}
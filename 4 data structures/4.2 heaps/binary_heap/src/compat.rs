@@ -0,0 +1,16 @@
+//! Aliases keeping APIs of former heap crates
+//!
+//! | crate                       | alias                                         |
+//! |-----------------------------|-----------------------------------------------|
+//! | `fixed_binary_heap`         | `Heap<T, Max, Cmp<C>, Fixed<T>>`              |
+//! | `fixed_minimum_binary_heap` | `Heap<T, Min, Identity, Fixed<T>>`            |
+//! | `keyed_fixed_binary_heap`   | `Heap<T, Form, ByKey, Fixed<T>>`              |
+//! | `delete_operation`          | `Heap<T, FixBinHeapForm, Identity, Fixed<T>>` |
+//!
+//! Former crates re-export these modules, so `collection`, `inspect` and `stable`
+//! APIs are inherited from `Heap` and `StableHeap`.
+
+pub mod delete_operation;
+pub mod fixed_binary_heap;
+pub mod fixed_minimum_binary_heap;
+pub mod keyed_fixed_binary_heap;
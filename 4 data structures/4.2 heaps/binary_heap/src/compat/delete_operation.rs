@@ -0,0 +1,222 @@
+//! `delete_operation` API
+//!
+//! `del` is simplified unless feature `complex-del` is enabled.
+
+pub use crate::{collection, inspect, MAX_LEVELS};
+
+use crate::{Fixed, Heap, Identity, Order};
+
+#[cfg(feature = "complex-del")]
+use crate::Storage;
+
+/// Defaults to `Maximal` as `Form`.
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
+pub enum FixBinHeapForm {
+    #[default]
+    Maximal,
+    Minimal,
+    Unspecified,
+}
+
+impl Order for FixBinHeapForm {
+    fn precedes<Q>(&self, a: &Q, b: &Q) -> bool
+    where
+        Q: PartialOrd + ?Sized,
+    {
+        match self {
+            FixBinHeapForm::Maximal => a > b,
+            FixBinHeapForm::Minimal => a < b,
+            FixBinHeapForm::Unspecified => panic!("Unsupported heap form."),
+        }
+    }
+}
+
+pub type FixBinHeap<T> = Heap<T, FixBinHeapForm, Identity, Fixed<T>>;
+
+impl<T> FixBinHeap<T>
+where
+    T: PartialOrd,
+{
+    pub fn new(levels: usize, form: FixBinHeapForm) -> Self {
+        Self::with_policies(levels, form, Identity)
+    }
+
+    /// Final capacity is aligned to maximal leaf capacity.
+    pub fn from_slice(slice: &[T], form: FixBinHeapForm) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_slice_with(slice, form, Identity)
+    }
+
+    /// Heap will support `Vec<T>` capacity nodes.
+    pub fn from_vec(vec: Vec<T>, form: FixBinHeapForm) -> Self {
+        Self::from_vec_with(vec, form, Identity)
+    }
+
+    /// Deletes item at level order index `ix`.
+    /// <br/>Last item replaces deleted one and moves up or down
+    /// as comparison with its new predecessor decides.
+    #[cfg(feature = "complex-del")]
+    pub fn del(&mut self, ix: usize) -> Result<(), ()> {
+        let len = self.len();
+
+        if ix >= len {
+            return Err(());
+        }
+
+        self.store.items_mut().swap(ix, len - 1);
+        self.store.pop();
+
+        if ix == len - 1 {
+            return Ok(());
+        }
+
+        if ix > 0 {
+            let pred_ix = (ix - 1) / 2;
+
+            if self.precedes(ix, pred_ix) {
+                self.sift_up(ix);
+                return Ok(());
+            }
+
+            // equal to predecessor, thus to be kept
+            if !self.precedes(pred_ix, ix) {
+                return Ok(());
+            }
+        }
+
+        self.sift_down(ix);
+        Ok(())
+    }
+
+    /// Deletes item at level order index `ix`.
+    /// <br/>Last item replaces deleted one and is tried to move both up and down.
+    #[cfg(not(feature = "complex-del"))]
+    pub fn del(&mut self, ix: usize) -> Result<(), ()> {
+        self.remove(ix).map(|_| ()).ok_or(())
+    }
+}
+
+// cargo test --features complex-del
+#[cfg(test)]
+mod tests_of_units {
+    use super::{FixBinHeap, FixBinHeapForm};
+    use crate::Storage;
+
+    // `layout` is stored as is, only its last item may violate heap property
+    fn del(form: FixBinHeapForm, layout: [u8; 12], ix: usize) -> Vec<u8> {
+        let mut heap = FixBinHeap::new(3, form);
+        for n in layout {
+            _ = heap.store.push(n);
+        }
+
+        assert_eq!(Ok(()), heap.del(ix));
+        assert_eq!(Ok(()), heap.validate());
+
+        heap.as_slice().to_vec()
+    }
+
+    #[test]
+    // equal-to-predecesor relation
+    fn no_bubbling() {
+        #[rustfmt::skip]
+        let test_cases = [
+            (
+                FixBinHeapForm::Minimal,
+                [1,  10, 1,  20, 30,  1, 1,  40, 50,  60, 70,  1],
+                [1,   1, 1,  20, 30,  1, 1,  40, 50,  60, 70],
+            ),
+            (
+                FixBinHeapForm::Maximal,
+                [70,  60, 1,  50, 40,  1, 1,  30, 20,  10, 0,  70],
+                [70,  70, 1,  50, 40,  1, 1,  30, 20,  10, 0],
+            ),
+        ];
+
+        for (form, layout, criterion) in test_cases {
+            assert_eq!(criterion, del(form, layout, 1).as_slice());
+        }
+    }
+
+    #[test]
+    // invalid-to-predecesor relation
+    fn bubble_up() {
+        #[rustfmt::skip]
+        let test_cases = [
+            (
+                FixBinHeapForm::Minimal,
+                [1,  10, 1,  20, 30,  1, 1,  40, 50,  60, 70,  1],
+                [1,   1, 1,  10, 30,  1, 1,  40, 20,  60, 70],
+            ),
+            (
+                FixBinHeapForm::Maximal,
+                [70,  60, 1,  50, 40,  1, 1,  30, 20,  10, 0,  70],
+                [70,  70, 1,  60, 40,  1, 1,  30, 50,  10, 0],
+            ),
+        ];
+
+        for (form, layout, criterion) in test_cases {
+            assert_eq!(criterion, del(form, layout, 8).as_slice());
+        }
+    }
+
+    #[test]
+    // purely-valid-to-predecesor relation
+    // descendants can be all: =, <, >
+    fn bubble_down() {
+        #[rustfmt::skip]
+        let test_cases = [
+            (
+                FixBinHeapForm::Minimal,
+                [1,  10, 1,  20, 30,  1, 1,  40, 50,  60, 70,  70],
+                [1,  20, 1,  40, 30,  1, 1,  70, 50,  60, 70],
+            ),
+            (
+                FixBinHeapForm::Maximal,
+                [70,  60, 1,  50, 40,  1, 1,  30, 20,  10, 0,  1],
+                [70,  50, 1,  30, 40,  1, 1,   1, 20,  10, 0],
+            ),
+        ];
+
+        for (form, layout, criterion) in test_cases {
+            assert_eq!(criterion, del(form, layout, 1).as_slice());
+        }
+    }
+
+    #[test]
+    fn general_properties() {
+        let test_cases = [(0, Ok(()), 0), (1, Err(()), 1), (2, Err(()), 1)];
+
+        for (ix, res, len) in test_cases {
+            let mut heap = FixBinHeap::from_vec(vec![0], FixBinHeapForm::Maximal);
+
+            assert_eq!(res, heap.del(ix));
+            assert_eq!(len, heap.len());
+        }
+    }
+
+    #[test]
+    fn extraction() {
+        let mut heap = FixBinHeap::from_slice(&[1, 2, 3, 4, 5, 6, 7], FixBinHeapForm::Maximal);
+
+        assert_eq!(Ok(()), heap.del(3));
+        assert_eq!(Err(()), heap.del(6));
+        assert_eq!(6, heap.len());
+
+        let mut rem = Vec::new();
+        while let Some(n) = heap.extract_root() {
+            rem.push(n);
+        }
+
+        assert_eq!(vec![7, 6, 5, 3, 2, 1], rem);
+    }
+
+    #[test]
+    #[should_panic(expected = "Unsupported heap form.")]
+    fn unspecified_form() {
+        let mut heap = FixBinHeap::new(1, FixBinHeapForm::Unspecified);
+        _ = heap.insert(1);
+        _ = heap.insert(2);
+    }
+}
@@ -0,0 +1,270 @@
+//! `fixed_binary_heap` API
+
+pub mod growable;
+pub mod stable;
+
+pub use crate::{collection, inspect};
+pub use crate::{Comparator as FixBinHeapCmp, Form as FixBinHeapForm, MAX_LEVELS};
+
+use crate::{Cmp, Fixed, Heap, Max};
+use std::cmp::Ordering;
+
+/// Item ordered as `Ordering::Greater` by `C` is closer to root.
+pub type FixBinHeap<T, C = FixBinHeapForm> = Heap<T, Max, Cmp<C>, Fixed<T>>;
+
+/// Orders items by key in `FixBinHeapForm` manner.
+pub struct KeyCmp<F> {
+    key: F,
+    form: FixBinHeapForm,
+}
+
+impl<T, K, F> FixBinHeapCmp<T> for KeyCmp<F>
+where
+    F: Fn(&T) -> K,
+    K: Ord,
+{
+    fn order(&self, a: &T, b: &T) -> Ordering {
+        let (a, b) = ((self.key)(a), (self.key)(b));
+
+        match self.form {
+            FixBinHeapForm::Maximal => a.cmp(&b),
+            FixBinHeapForm::Minimal => b.cmp(&a),
+        }
+    }
+}
+
+impl<T, C> FixBinHeap<T, C>
+where
+    C: FixBinHeapCmp<T>,
+{
+    pub fn new(levels: usize, form: C) -> Self {
+        Self::with_policies(levels, Max, Cmp(form))
+    }
+
+    /// Final capacity is aligned to maximal leaf capacity.
+    pub fn from_slice(slice: &[T], form: C) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_slice_with(slice, Max, Cmp(form))
+    }
+
+    /// Heap will support `Vec<T>` capacity nodes.
+    pub fn from_vec(vec: Vec<T>, form: C) -> Self {
+        Self::from_vec_with(vec, Max, Cmp(form))
+    }
+}
+
+impl<T, F> FixBinHeap<T, F>
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    /// Root is maximal item by `cmp`.
+    /// <br/>Use reversed `cmp` for minimal root.
+    pub fn with_comparator(levels: usize, cmp: F) -> Self {
+        FixBinHeap::new(levels, cmp)
+    }
+}
+
+impl<T, K, F> FixBinHeap<T, KeyCmp<F>>
+where
+    F: Fn(&T) -> K,
+    K: Ord,
+{
+    /// Root is maximal or minimal item by `key` depending on `form`.
+    pub fn by_key(levels: usize, form: FixBinHeapForm, key: F) -> Self {
+        FixBinHeap::new(levels, KeyCmp { key, form })
+    }
+}
+
+#[cfg(test)]
+mod tests_of_units {
+    use super::{FixBinHeap, FixBinHeapForm, MAX_LEVELS};
+    use std::rc::Rc;
+
+    // neither `Clone` nor `Default`
+    struct Job {
+        name: String,
+        cost: u32,
+    }
+
+    fn job(name: &str, cost: u32) -> Job {
+        Job {
+            name: name.to_string(),
+            cost,
+        }
+    }
+
+    #[test]
+    fn new() {
+        let heap = FixBinHeap::<u64>::new(2, FixBinHeapForm::Minimal);
+
+        assert_eq!(0, heap.len());
+        assert_eq!(7, heap.capacity());
+    }
+
+    #[test]
+    #[should_panic(expected = "Maximum supported levels is 25. 0 for root only.")]
+    fn unsupported_level_count() {
+        _ = FixBinHeap::<u64>::new(26, FixBinHeapForm::Minimal);
+    }
+
+    #[test]
+    fn insertion() {
+        #[rustfmt::skip]
+        let test_cases = [
+            (FixBinHeapForm::Maximal, [1, 2, 2, 4, 5, 6, 7, 7, 8, 9], [9, 8, 6, 7, 7, 2, 5, 1, 4, 2]),
+            (FixBinHeapForm::Minimal, [9, 8, 7, 7, 6, 5, 4, 2, 2, 1], [1, 2, 5, 4, 2, 8, 6, 9, 7, 7]),
+        ];
+
+        for (form, nums, criterion) in test_cases {
+            let mut heap = FixBinHeap::<i16>::new(3, form);
+
+            for (ix, n) in nums.into_iter().enumerate() {
+                assert_eq!(Ok(()), heap.insert(n));
+                assert_eq!(ix + 1, heap.len());
+            }
+
+            assert_eq!(criterion, heap.as_slice());
+        }
+
+        let mut heap = FixBinHeap::<usize>::new(0, FixBinHeapForm::Minimal);
+        assert_eq!(Ok(()), heap.insert(0));
+        assert_eq!(Err(()), heap.insert(0));
+    }
+
+    #[test]
+    fn extraction() {
+        let test_cases = [
+            (FixBinHeapForm::Minimal, [8, 10, 9, 10], [8, 9, 10, 10]),
+            (FixBinHeapForm::Maximal, [10, 8, 9, 8], [10, 9, 8, 8]),
+        ];
+
+        for (form, nums, criterion) in test_cases {
+            let mut heap = FixBinHeap::from_slice(&nums, form);
+            assert_eq!(Some(&criterion[0]), heap.peek_root());
+
+            for (ix, n) in criterion.into_iter().enumerate() {
+                assert_eq!(Some(n), heap.extract_root());
+                assert_eq!(3 - ix, heap.len());
+            }
+
+            assert_eq!(None, heap.extract_root());
+            assert_eq!(None, heap.peek_root());
+        }
+    }
+
+    #[test]
+    fn from_vec() {
+        let mut vec = Vec::with_capacity(5);
+        vec.extend([9, 8, 7, 6]);
+        let ptr = vec.as_ptr();
+
+        let heap = FixBinHeap::from_vec(vec, FixBinHeapForm::Minimal);
+        assert_eq!(4, heap.len());
+        assert_eq!(5, heap.capacity());
+        assert_eq!([6, 8, 7, 9], heap.as_slice());
+        assert_eq!(ptr, heap.as_slice().as_ptr());
+
+        // single item, once underflowing
+        let heap = FixBinHeap::from_vec(vec![1], FixBinHeapForm::Minimal);
+        assert_eq!(Some(&1), heap.peek_root());
+
+        let heap = FixBinHeap::from_vec((0..10).rev().collect(), FixBinHeapForm::Minimal);
+        assert_eq!([0, 1, 3, 2, 5, 4, 7, 9, 6, 8], heap.as_slice());
+    }
+
+    #[test]
+    fn from_slice() {
+        let heap = FixBinHeap::from_slice(&[9, 8, 7, 6], FixBinHeapForm::Minimal);
+
+        assert_eq!(4, heap.len());
+        assert_eq!(7, heap.capacity());
+        assert_eq!([6, 8, 7, 9], heap.as_slice());
+    }
+
+    #[test]
+    #[should_panic(expected = "Input length is greater than maximal heap item count support.")]
+    fn from_slice_limit() {
+        #[derive(PartialEq, PartialOrd, Clone)]
+        struct ZeroSize();
+
+        let len = 2usize.pow(MAX_LEVELS as u32) + 1;
+        let mut vec = Vec::<ZeroSize>::with_capacity(len);
+
+        unsafe {
+            vec.set_len(len);
+        }
+
+        _ = FixBinHeap::from_slice(&vec, FixBinHeapForm::Minimal);
+    }
+
+    #[test]
+    fn with_comparator() {
+        let mut heap = FixBinHeap::with_comparator(2, |a: &Job, b: &Job| b.cost.cmp(&a.cost));
+
+        for (name, cost) in [("c", 3), ("a", 1), ("d", 4), ("b", 2)] {
+            assert_eq!(Ok(()), heap.insert(job(name, cost)));
+        }
+
+        assert_eq!(Some("a"), heap.peek_root().map(|j| j.name.as_str()));
+
+        for name in ["a", "b", "c", "d"] {
+            assert_eq!(Some(name.to_string()), heap.extract_root().map(|j| j.name));
+        }
+
+        assert!(heap.extract_root().is_none());
+    }
+
+    #[test]
+    fn by_key() {
+        let jobs = [("x", 7), ("y", 2), ("z", 9)];
+
+        let mut max = FixBinHeap::by_key(1, FixBinHeapForm::Maximal, |j: &Job| j.cost);
+        let mut min = FixBinHeap::by_key(1, FixBinHeapForm::Minimal, |j: &Job| j.cost);
+        for (name, cost) in jobs {
+            _ = max.insert(job(name, cost));
+            _ = min.insert(job(name, cost));
+        }
+
+        assert_eq!(Err(()), max.insert(job("w", 0)));
+
+        for cost in [9, 7, 2] {
+            assert_eq!(Some(cost), max.extract_root().map(|j| j.cost));
+        }
+
+        for cost in [2, 7, 9] {
+            assert_eq!(Some(cost), min.extract_root().map(|j| j.cost));
+        }
+    }
+
+    #[test]
+    fn drops_items() {
+        let item = Rc::new(0);
+
+        let mut heap = FixBinHeap::by_key(2, FixBinHeapForm::Minimal, |rc: &Rc<i32>| **rc);
+        for _ in 0..5 {
+            _ = heap.insert(Rc::clone(&item));
+        }
+
+        assert_eq!(6, Rc::strong_count(&item));
+
+        let root = heap.extract_root();
+        assert_eq!(6, Rc::strong_count(&item));
+
+        drop(root);
+        assert_eq!(5, Rc::strong_count(&item));
+
+        drop(heap);
+        assert_eq!(1, Rc::strong_count(&item));
+    }
+
+    #[test]
+    fn from_vec_non_clone() {
+        let jobs = vec![job("b", 2), job("c", 3), job("a", 1)];
+        let mut heap = FixBinHeap::from_vec(jobs, |a: &Job, b: &Job| b.name.cmp(&a.name));
+
+        assert_eq!(Some(1), heap.extract_root().map(|j| j.cost));
+        assert_eq!(Some(2), heap.extract_root().map(|j| j.cost));
+    }
+}
@@ -0,0 +1,157 @@
+use super::{FixBinHeapCmp, FixBinHeapForm};
+use crate::{Cmp, Growable, Heap, Max};
+
+/// Binary heap that adds one level when full.
+/// <br/>Unlike `FixBinHeap` levels are not limited by `MAX_LEVELS`, `insert` never fails.
+pub type GrowBinHeap<T, C = FixBinHeapForm> = Heap<T, Max, Cmp<C>, Growable<T>>;
+
+impl<T, C> GrowBinHeap<T, C>
+where
+    C: FixBinHeapCmp<T>,
+{
+    /// Starts with root only.
+    pub fn new(form: C) -> Self {
+        Self::with_levels(0, form)
+    }
+
+    pub fn with_levels(levels: usize, form: C) -> Self {
+        Self::with_policies(levels, Max, Cmp(form))
+    }
+
+    /// Capacity is aligned to maximal leaf capacity of needed levels.
+    pub fn from_slice(slice: &[T], form: C) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_vec(slice.to_vec(), form)
+    }
+
+    /// Capacity is aligned to maximal leaf capacity of needed levels.
+    pub fn from_vec(vec: Vec<T>, form: C) -> Self {
+        Self::from_vec_with(vec, Max, Cmp(form))
+    }
+}
+
+#[cfg(test)]
+mod tests_of_units {
+    use super::super::FixBinHeapForm;
+    use super::GrowBinHeap;
+
+    #[test]
+    fn new() {
+        let heap = GrowBinHeap::<u64>::new(FixBinHeapForm::Maximal);
+
+        assert_eq!(0, heap.levels());
+        assert_eq!(1, heap.capacity());
+        assert!(heap.is_empty());
+
+        let heap = GrowBinHeap::<u64>::with_levels(2, FixBinHeapForm::Minimal);
+
+        assert_eq!(2, heap.levels());
+        assert_eq!(7, heap.capacity());
+    }
+
+    #[test]
+    fn growing() {
+        let mut heap = GrowBinHeap::<u32>::new(FixBinHeapForm::Minimal);
+
+        let caps = [1, 3, 3, 7, 7, 7, 7, 15];
+        for (n, cap) in (0..8).rev().zip(caps) {
+            assert_eq!(Ok(()), heap.insert(n));
+            assert_eq!(cap, heap.capacity());
+        }
+
+        assert_eq!(3, heap.levels());
+        assert_eq!(8, heap.len());
+
+        for n in 0..8 {
+            assert_eq!(Some(n), heap.extract_root());
+        }
+
+        assert_eq!(None, heap.extract_root());
+    }
+
+    #[test]
+    fn beyond_max_levels() {
+        #[derive(PartialEq, PartialOrd)]
+        struct ZeroSize();
+
+        let len = 2usize.pow(26) - 1;
+        let mut vec = Vec::<ZeroSize>::with_capacity(len);
+
+        unsafe {
+            vec.set_len(len);
+        }
+
+        let mut heap = GrowBinHeap::from_vec(vec, FixBinHeapForm::Maximal);
+        assert_eq!(25, heap.levels());
+
+        assert_eq!(Ok(()), heap.insert(ZeroSize()));
+        assert_eq!(26, heap.levels());
+    }
+
+    #[test]
+    fn shrink_to_fit() {
+        let mut heap = GrowBinHeap::with_levels(4, FixBinHeapForm::Minimal);
+        for n in [5, 3, 4, 1] {
+            _ = heap.insert(n);
+        }
+
+        heap.shrink_to_fit();
+        assert_eq!(2, heap.levels());
+        assert_eq!(7, heap.capacity());
+        assert_eq!([1, 3, 4, 5], heap.as_slice());
+
+        // no growth by shrinking
+        heap.shrink_to_fit();
+        assert_eq!(7, heap.capacity());
+
+        while heap.extract_root().is_some() {}
+        heap.shrink_to_fit();
+        assert_eq!(0, heap.levels());
+        assert_eq!(1, heap.capacity());
+
+        _ = heap.insert(9);
+        assert_eq!(Some(&9), heap.peek_root());
+    }
+
+    #[test]
+    fn from_vec() {
+        let heap = GrowBinHeap::from_vec(vec![9, 8, 7, 6], FixBinHeapForm::Minimal);
+
+        assert_eq!(2, heap.levels());
+        assert_eq!(7, heap.capacity());
+        assert_eq!([6, 8, 7, 9], heap.as_slice());
+
+        let heap = GrowBinHeap::from_vec(vec![3], FixBinHeapForm::Maximal);
+        assert_eq!(0, heap.levels());
+        assert_eq!(Some(&3), heap.peek_root());
+
+        let mut heap = GrowBinHeap::from_slice(&[1, 2, 3], FixBinHeapForm::Maximal);
+        assert_eq!(1, heap.levels());
+        assert_eq!([3, 2, 1], heap.as_slice());
+
+        _ = heap.insert(4);
+        assert_eq!(2, heap.levels());
+        assert_eq!(Some(&4), heap.peek_root());
+    }
+
+    #[test]
+    fn non_clone_items() {
+        struct Task {
+            prio: u8,
+            _payload: Vec<u8>,
+        }
+
+        let mut heap = GrowBinHeap::new(|a: &Task, b: &Task| a.prio.cmp(&b.prio));
+        for prio in [3, 9, 1] {
+            _ = heap.insert(Task {
+                prio,
+                _payload: vec![prio; 4],
+            });
+        }
+
+        assert_eq!(Some(9), heap.extract_root().map(|t| t.prio));
+        assert_eq!(Some(3), heap.extract_root().map(|t| t.prio));
+    }
+}
@@ -0,0 +1,54 @@
+pub use crate::stable::Ties;
+
+use super::{FixBinHeapCmp, FixBinHeapForm};
+use crate::stable::StableHeap;
+use crate::{Cmp, Max};
+
+/// `FixBinHeap` extracting equal items in insertion order or reversed
+pub type StableFixBinHeap<T, C = FixBinHeapForm> = StableHeap<T, Max, Cmp<C>>;
+
+impl<T, C> StableFixBinHeap<T, C>
+where
+    C: FixBinHeapCmp<T>,
+{
+    pub fn new(levels: usize, form: C, ties: Ties) -> Self {
+        Self::with_policies(levels, Max, Cmp(form), ties)
+    }
+}
+
+#[cfg(test)]
+mod tests_of_units {
+    use super::{FixBinHeapForm, StableFixBinHeap, Ties};
+
+    #[test]
+    fn form() {
+        let mut heap = StableFixBinHeap::new(1, FixBinHeapForm::Maximal, Ties::Fifo);
+        for n in [1, 3, 3] {
+            _ = heap.insert(n);
+        }
+
+        assert_eq!(Err(()), heap.insert(4));
+        assert_eq!(Some(3), heap.extract_root());
+        assert_eq!(Some(3), heap.extract_root());
+        assert_eq!(Some(1), heap.extract_root());
+    }
+
+    #[test]
+    fn comparator() {
+        let by_prio = |a: &(u8, char), b: &(u8, char)| a.0.cmp(&b.0);
+
+        for (ties, criterion) in [(Ties::Fifo, "bdac"), (Ties::Lifo, "dbca")] {
+            let mut heap = StableFixBinHeap::new(2, by_prio, ties);
+            for job in [(1, 'a'), (2, 'b'), (1, 'c'), (2, 'd')] {
+                assert_eq!(Ok(()), heap.insert(job));
+            }
+
+            let mut names = String::new();
+            while let Some(job) = heap.extract_root() {
+                names.push(job.1);
+            }
+
+            assert_eq!(criterion, names);
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! `fixed_minimum_binary_heap` API
+
+pub mod stable;
+
+pub use crate::{collection, inspect, MAX_LEVELS};
+
+use crate::{Fixed, Heap, Identity, Min};
+
+pub type FixMinBinHeap<T> = Heap<T, Min, Identity, Fixed<T>>;
+
+/// Final capacity is aligned to maximal leaf capacity.
+impl<T> From<&[T]> for FixMinBinHeap<T>
+where
+    T: PartialOrd + Clone,
+{
+    fn from(slice: &[T]) -> Self {
+        Self::from_slice_with(slice, Min, Identity)
+    }
+}
+
+/// Heap will support `Vec<T>` capacity nodes.
+impl<T> From<Vec<T>> for FixMinBinHeap<T>
+where
+    T: PartialOrd,
+{
+    fn from(vec: Vec<T>) -> Self {
+        Self::from_vec_with(vec, Min, Identity)
+    }
+}
+
+#[cfg(test)]
+mod tests_of_units {
+    use super::FixMinBinHeap;
+
+    #[test]
+    fn new() {
+        let heap = FixMinBinHeap::<u64>::new(2);
+
+        assert_eq!(0, heap.len());
+        assert_eq!(7, heap.capacity());
+        assert_eq!(None, heap.peek_min());
+    }
+
+    #[test]
+    fn insertion() {
+        let mut heap = FixMinBinHeap::<i16>::new(3);
+
+        for n in [9, 8, 7, 7, 6, 5, 4, 2, 2, 1] {
+            assert_eq!(Ok(()), heap.insert(n));
+        }
+
+        assert_eq!([1, 2, 5, 4, 2, 8, 6, 9, 7, 7], heap.as_slice());
+
+        let mut heap = FixMinBinHeap::<usize>::new(0);
+        assert_eq!(Ok(()), heap.insert(0));
+        assert_eq!(Err(()), heap.insert(0));
+    }
+
+    #[test]
+    fn extraction() {
+        let mut heap = FixMinBinHeap::from(vec![8, 10, 9, 10]);
+        assert_eq!(Some(&8), heap.peek_min());
+
+        for (ix, n) in [8, 9, 10, 10].into_iter().enumerate() {
+            assert_eq!(Some(n), heap.extract_min());
+            assert_eq!(3 - ix, heap.len());
+        }
+
+        assert_eq!(None, heap.extract_min());
+    }
+
+    #[test]
+    fn from() {
+        let mut vec = Vec::with_capacity(5);
+        vec.extend([9, 8, 7, 6]);
+
+        let heap = FixMinBinHeap::from(vec);
+        assert_eq!(5, heap.capacity());
+        assert_eq!([6, 8, 7, 9], heap.as_slice());
+
+        let heap = FixMinBinHeap::from(&[9, 8, 7, 6][..]);
+        assert_eq!(7, heap.capacity());
+        assert_eq!([6, 8, 7, 9], heap.as_slice());
+
+        // single item, once underflowing
+        let heap = FixMinBinHeap::from(vec![1]);
+        assert_eq!(Some(&1), heap.peek_min());
+    }
+}
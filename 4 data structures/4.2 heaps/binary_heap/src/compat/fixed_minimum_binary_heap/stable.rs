@@ -0,0 +1,54 @@
+pub use crate::stable::Ties;
+
+use crate::stable::StableHeap;
+use crate::{Identity, Min};
+
+/// `FixMinBinHeap` extracting equal items in insertion order or reversed
+pub type StableFixMinBinHeap<T> = StableHeap<T, Min, Identity>;
+
+impl<T> StableFixMinBinHeap<T>
+where
+    T: PartialOrd,
+{
+    pub fn new(levels: usize, ties: Ties) -> Self {
+        Self::with_policies(levels, Min, Identity, ties)
+    }
+}
+
+#[cfg(test)]
+mod tests_of_units {
+    use super::{StableFixMinBinHeap, Ties};
+    use std::cmp::Ordering;
+
+    // ordered by `prio` only
+    #[derive(PartialEq, Debug)]
+    struct Job {
+        prio: u8,
+        name: char,
+    }
+
+    impl PartialOrd for Job {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            self.prio.partial_cmp(&other.prio)
+        }
+    }
+
+    #[test]
+    fn ties() {
+        for (ties, criterion) in [(Ties::Fifo, "bdac"), (Ties::Lifo, "dbca")] {
+            let mut heap = StableFixMinBinHeap::new(2, ties);
+            for (prio, name) in [(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd')] {
+                assert_eq!(Ok(()), heap.insert(Job { prio, name }));
+            }
+
+            assert_eq!(Some(1), heap.peek_min().map(|j| j.prio));
+
+            let mut names = String::new();
+            while let Some(job) = heap.extract_min() {
+                names.push(job.name);
+            }
+
+            assert_eq!(criterion, names);
+        }
+    }
+}
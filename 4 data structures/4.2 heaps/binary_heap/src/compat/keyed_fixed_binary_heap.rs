@@ -0,0 +1,129 @@
+//! `keyed_fixed_binary_heap` API
+
+pub mod stable;
+
+pub use crate::{collection, inspect};
+pub use crate::{Form as FixBinHeapForm, HeapKey as FixBinHeapKey, MAX_LEVELS};
+
+use crate::{ByKey, Fixed, Heap};
+
+pub type FixBinHeap<T> = Heap<T, FixBinHeapForm, ByKey, Fixed<T>>;
+
+/// Inverted key, `Maximal` heap yields least number first.
+impl FixBinHeapKey for u16 {
+    type Key = u16;
+    fn key(&self) -> u16 {
+        u16::MAX - *self
+    }
+}
+
+impl FixBinHeapKey for usize {
+    type Key = usize;
+    fn key(&self) -> usize {
+        let modul = self % 10;
+        self - modul + 9 - modul
+
+        // so this maps
+        // +-----------+---+-------------+---+-----------+
+        // |  0 => 9   | … |   5 => 4    | … |   9 => 0  |
+        // +-----------+---+-------------+---+-----------+
+        // |    ⋮      | ⋮ |     ⋮       | ⋮ |     ⋮     |
+        // +-----------+---+-------------+---+-----------+
+    }
+}
+
+impl<T> FixBinHeap<T>
+where
+    T: FixBinHeapKey,
+{
+    pub fn new(levels: usize, form: FixBinHeapForm) -> Self {
+        Self::with_policies(levels, form, ByKey)
+    }
+
+    /// Final capacity is aligned to maximal leaf capacity.
+    pub fn from_slice(slice: &[T], form: FixBinHeapForm) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_slice_with(slice, form, ByKey)
+    }
+
+    /// Heap will support `Vec<T>` capacity nodes.
+    pub fn from_vec(vec: Vec<T>, form: FixBinHeapForm) -> Self {
+        Self::from_vec_with(vec, form, ByKey)
+    }
+}
+
+#[cfg(test)]
+mod tests_of_units {
+    use super::{FixBinHeap, FixBinHeapForm, FixBinHeapKey};
+
+    #[derive(Clone, PartialEq, Debug)]
+    struct Item {
+        key: u8,
+        val: char,
+    }
+
+    impl FixBinHeapKey for Item {
+        type Key = u8;
+        fn key(&self) -> u8 {
+            self.key
+        }
+    }
+
+    #[test]
+    fn basic_test() {
+        let items = [(2, 'b'), (3, 'c'), (1, 'a')].map(|(key, val)| Item { key, val });
+
+        let mut heap = FixBinHeap::from_slice(&items, FixBinHeapForm::Maximal);
+        assert_eq!(Some('c'), heap.extract_root().map(|i| i.val));
+
+        let mut heap = FixBinHeap::from_vec(items.to_vec(), FixBinHeapForm::Minimal);
+        assert_eq!(Some('a'), heap.extract_root().map(|i| i.val));
+
+        let mut heap = FixBinHeap::new(0, FixBinHeapForm::Minimal);
+        assert_eq!(1, heap.capacity());
+        assert_eq!(Ok(()), heap.insert(items[0].clone()));
+        assert_eq!(Err(()), heap.insert(items[1].clone()));
+    }
+
+    #[test]
+    fn u16_heap() {
+        let mut heap = FixBinHeap::<u16>::new(2, FixBinHeapForm::Maximal);
+
+        for n in [7, 0, u16::MAX, 3] {
+            assert_eq!(Ok(()), heap.insert(n));
+        }
+
+        for n in [0, 3, 7, u16::MAX] {
+            assert_eq!(Some(n), heap.extract_root());
+        }
+    }
+
+    mod fix_bin_heap_key {
+
+        use super::super::FixBinHeapKey;
+        #[test]
+        fn u16_key_test() {
+            assert_eq!(0, u16::MAX.key());
+            assert_eq!(1, u16::MAX - 1u16.key());
+            assert_eq!(u16::MAX, 0u16.key());
+            assert_eq!(u16::MAX - 1, 1u16.key());
+        }
+
+        #[test]
+        fn usize_key_test() {
+            assert_eq!(0, 9usize.key());
+            assert_eq!(5, 4usize.key());
+            assert_eq!(9, 0usize.key());
+
+            assert_eq!(100, 109usize.key());
+            assert_eq!(105, 104usize.key());
+            assert_eq!(109, 100usize.key());
+
+            assert_eq!(1990, 1999usize.key());
+            assert_eq!(1995, 1994usize.key());
+            assert_eq!(1999, 1990usize.key());
+        }
+    }
+}
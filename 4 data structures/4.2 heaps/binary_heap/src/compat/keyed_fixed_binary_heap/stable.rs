@@ -0,0 +1,61 @@
+pub use crate::stable::Ties;
+
+use super::{FixBinHeapForm, FixBinHeapKey};
+use crate::stable::StableHeap;
+use crate::ByKey;
+
+/// `FixBinHeap` extracting items with equal keys in insertion order or reversed
+pub type StableFixBinHeap<T> = StableHeap<T, FixBinHeapForm, ByKey>;
+
+impl<T> StableFixBinHeap<T>
+where
+    T: FixBinHeapKey,
+{
+    pub fn new(levels: usize, form: FixBinHeapForm, ties: Ties) -> Self {
+        Self::with_policies(levels, form, ByKey, ties)
+    }
+}
+
+#[cfg(test)]
+mod tests_of_units {
+    use super::{FixBinHeapForm, FixBinHeapKey, StableFixBinHeap, Ties};
+
+    struct Job {
+        prio: u8,
+        name: char,
+    }
+
+    impl FixBinHeapKey for Job {
+        type Key = u8;
+        fn key(&self) -> u8 {
+            self.prio
+        }
+    }
+
+    #[test]
+    fn ties() {
+        #[rustfmt::skip]
+        let test_cases = [
+            (FixBinHeapForm::Minimal, Ties::Fifo, "bdac"),
+            (FixBinHeapForm::Maximal, Ties::Lifo, "cadb"),
+        ];
+
+        for (form, ties, criterion) in test_cases {
+            let mut heap = StableFixBinHeap::new(2, form, ties);
+            for (prio, name) in [(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd')] {
+                assert_eq!(Ok(()), heap.insert(Job { prio, name }));
+            }
+
+            let mut names = String::new();
+            while let Some(job) = heap.extract_root() {
+                names.push(job.name);
+            }
+
+            assert_eq!(criterion, names);
+        }
+
+        let mut heap = StableFixBinHeap::new(0, FixBinHeapForm::Minimal, Ties::Lifo);
+        assert_eq!(Ok(()), heap.insert(Job { prio: 1, name: 'a' }));
+        assert!(heap.insert(Job { prio: 0, name: 'b' }).is_err());
+    }
+}
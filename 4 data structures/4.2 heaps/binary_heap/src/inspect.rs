@@ -0,0 +1,243 @@
+//! Heap inspection
+//!
+//! - `validate` — heap property check
+//! - `to_dot` — Graphviz graph, nodes of each level share rank
+//! - `to_ascii` — levels line by line, free slots as `_`
+
+use super::{Heap, KeyFn, Order, Storage};
+use std::fmt::{Debug, Write};
+
+impl<T, O, K, S> Heap<T, O, K, S>
+where
+    O: Order,
+    K: KeyFn<T>,
+    S: Storage<T>,
+{
+    /// Checks that no item should be closer to root than its parent.
+    /// <br/>Returns first violating `(parent, child)` indices in level order.
+    pub fn validate(&self) -> Result<(), (usize, usize)> {
+        violation(self.len(), |p, c| self.precedes(c, p))
+    }
+
+    pub fn to_dot(&self) -> String
+    where
+        T: Debug,
+    {
+        dot(self.as_slice())
+    }
+
+    pub fn to_ascii(&self) -> String
+    where
+        T: Debug,
+    {
+        ascii(self.as_slice(), self.capacity())
+    }
+}
+
+// `precedes(parent, child)` is `true` when child belongs above parent
+fn violation(
+    len: usize,
+    mut precedes: impl FnMut(usize, usize) -> bool,
+) -> Result<(), (usize, usize)> {
+    for child in 1..len {
+        let parent = (child - 1) / 2;
+
+        if precedes(parent, child) {
+            return Err((parent, child));
+        }
+    }
+
+    Ok(())
+}
+
+fn dot<T>(items: &[T]) -> String
+where
+    T: Debug,
+{
+    let mut dot = String::from("digraph heap {\n    node [shape=circle];\n");
+
+    for (ix, item) in items.iter().enumerate() {
+        let label = format!("{:?}", item)
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"");
+        _ = writeln!(dot, "    n{} [label=\"{}\"];", ix, label);
+
+        if ix > 0 {
+            _ = writeln!(dot, "    n{} -> n{};", (ix - 1) / 2, ix);
+        }
+    }
+
+    for (level, range) in levels(items.len()).enumerate() {
+        let nodes = range.map(|ix| format!("n{};", ix)).collect::<Vec<String>>();
+        _ = writeln!(
+            dot,
+            "    {{ rank=same; {} }} // level {}",
+            nodes.join(" "),
+            level
+        );
+    }
+
+    dot.push_str("}\n");
+    dot
+}
+
+/// Renders each level as line. Levels with no item are collapsed into
+/// one line, levels are counted up to capacity `cap`.
+fn ascii<T>(items: &[T], cap: usize) -> String
+where
+    T: Debug,
+{
+    let mut ascii = String::new();
+
+    let cap_levels = levels(cap).count();
+    let mut level = 0;
+
+    for range in levels(cap) {
+        if range.start >= items.len() {
+            break;
+        }
+
+        let line = range
+            .map(|ix| match items.get(ix) {
+                Some(item) => format!("{:?}", item),
+                None => String::from("_"),
+            })
+            .collect::<Vec<String>>();
+
+        _ = writeln!(ascii, "{} │ {}", level, line.join(" "));
+        level += 1;
+    }
+
+    if level + 1 == cap_levels {
+        _ = writeln!(ascii, "{} │ empty", level);
+    } else if level < cap_levels {
+        _ = writeln!(ascii, "{}..={} │ empty", level, cap_levels - 1);
+    }
+
+    ascii
+}
+
+// index ranges of levels holding `len` nodes, last one possibly partial
+fn levels(len: usize) -> impl Iterator<Item = std::ops::Range<usize>> {
+    (0..usize::BITS)
+        .map(|level| (1usize << level) - 1)
+        .take_while(move |start| *start < len)
+        .map(move |start| start..(2 * start + 1).min(len))
+}
+
+#[cfg(test)]
+mod tests_of_units {
+    use super::{ascii, dot, violation};
+    use crate::{ByKey, Form, Heap, HeapKey, Max, MaxHeap, MinHeap, Storage};
+
+    #[test]
+    fn validate() {
+        let mut heap = MaxHeap::new(2);
+        assert_eq!(Ok(()), heap.validate());
+
+        for n in [3, 9, 4, 1, 7] {
+            _ = heap.insert(n);
+        }
+
+        assert_eq!(Ok(()), heap.validate());
+        assert_eq!(&[9, 7, 4, 1, 3], heap.as_slice());
+
+        heap.store.items_mut()[3] = 8;
+        assert_eq!(Err((1, 3)), heap.validate());
+
+        let mut heap =
+            MinHeap::from_vec_with(vec![3, 9, 4, 1, 7], Default::default(), Default::default());
+        assert_eq!(&[1, 3, 4, 9, 7], heap.as_slice());
+
+        heap.store.items_mut()[2] = 0;
+        assert_eq!(Err((0, 2)), heap.validate());
+    }
+
+    #[test]
+    fn validate_by_key() {
+        #[derive(Debug)]
+        struct Inverted(u8);
+
+        impl HeapKey for Inverted {
+            type Key = u8;
+            fn key(&self) -> u8 {
+                u8::MAX - self.0
+            }
+        }
+
+        let mut heap = Heap::<_, Form, ByKey>::with_policies(2, Form::Maximal, ByKey);
+        for n in [3, 9, 4, 1, 7] {
+            _ = heap.insert(Inverted(n));
+        }
+
+        assert_eq!(Ok(()), heap.validate());
+
+        heap.store.items_mut()[4] = Inverted(0);
+        assert_eq!(Err((1, 4)), heap.validate());
+    }
+
+    #[test]
+    fn violation_test() {
+        let items = [1, 2, 3, 0, 5, 6, 2];
+
+        assert_eq!(
+            Err((1, 3)),
+            violation(items.len(), |p, c| items[c] < items[p])
+        );
+        assert_eq!(
+            Err((0, 1)),
+            violation(items.len(), |p, c| items[c] > items[p])
+        );
+        assert_eq!(Ok(()), violation(1, |_, _| true));
+    }
+
+    #[test]
+    fn dot_test() {
+        let criterion = "digraph heap {
+    node [shape=circle];
+    n0 [label=\"\\\"c\\\"\"];
+    n1 [label=\"\\\"b\\\"\"];
+    n0 -> n1;
+    n2 [label=\"\\\"a\\\"\"];
+    n0 -> n2;
+    n3 [label=\"\\\"a\\\"\"];
+    n1 -> n3;
+    { rank=same; n0; } // level 0
+    { rank=same; n1; n2; } // level 1
+    { rank=same; n3; } // level 2
+}
+";
+
+        assert_eq!(criterion, dot(&["c", "b", "a", "a"]));
+    }
+
+    #[test]
+    fn ascii_test() {
+        #[rustfmt::skip]
+        let test_cases = [
+            (vec![], 1, "0 │ empty\n"),
+            (vec![5], 1, "0 │ 5\n"),
+            (vec![5, 4, 3, 2], 7, "0 │ 5\n1 │ 4 3\n2 │ 2 _ _ _\n"),
+            (vec![5, 4], 15, "0 │ 5\n1 │ 4 _\n2..=3 │ empty\n"),
+            (vec![5, 4, 3], 7, "0 │ 5\n1 │ 4 3\n2 │ empty\n"),
+            (vec![5, 4, 3], 5, "0 │ 5\n1 │ 4 3\n2 │ empty\n"),
+        ];
+
+        for (items, cap, criterion) in test_cases {
+            assert_eq!(criterion, ascii(&items, cap), "{:?} {}", items, cap);
+        }
+    }
+
+    #[test]
+    fn heap_renderers() {
+        let mut heap = MinHeap::new(1);
+        _ = heap.insert(2);
+        _ = heap.insert(1);
+
+        assert_eq!("0 │ 1\n1 │ 2 _\n", heap.to_ascii());
+        assert!(heap.to_dot().contains("n0 -> n1;"));
+
+        let heap = Heap::<_, Max>::from_vec_with(vec![1, 2], Max, Default::default());
+        assert_eq!("0 │ 2\n1 │ 1\n", heap.to_ascii());
+    }
+}
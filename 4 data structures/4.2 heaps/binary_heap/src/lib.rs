@@ -0,0 +1,804 @@
+//! Policy based binary heap
+//!
+//! `Heap<T, O, K, S>` is configured by type parameters
+//! - `O: Order` — which item goes to root: `Min`, `Max` or runtime `Form`
+//! - `K: KeyFn<T>` — what is compared: `Identity`, `ByKey`, `FnKey` or `Cmp`
+//! - `S: Storage<T>` — capacity: `Fixed` given by levels or `Growable`
+//!
+//! Module `collection` adds `std::collections::BinaryHeap` like API, `inspect`
+//! heap property check and renderers, all of them for every policy combination.
+//!
+//! Module `compat` keeps APIs of `fixed_binary_heap`, `fixed_minimum_binary_heap`,
+//! `keyed_fixed_binary_heap` and `delete_operation` crates as aliases.
+
+pub mod collection;
+pub mod compat;
+pub mod inspect;
+pub mod stable;
+
+use std::cmp::Ordering;
+use std::marker::PhantomData;
+
+pub const MAX_LEVELS: usize = 25;
+
+/// Root placement policy
+pub trait Order {
+    /// `true` when `a` belongs closer to root than `b`
+    fn precedes<Q>(&self, a: &Q, b: &Q) -> bool
+    where
+        Q: PartialOrd + ?Sized;
+}
+
+/// Least item is root.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Min;
+
+/// Greatest item is root.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Max;
+
+/// Root placement selected at runtime
+/// <br/>Defaults to `Maximal` as `std::collections::BinaryHeap`.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum Form {
+    #[default]
+    Maximal,
+    Minimal,
+}
+
+impl Order for Min {
+    fn precedes<Q>(&self, a: &Q, b: &Q) -> bool
+    where
+        Q: PartialOrd + ?Sized,
+    {
+        a < b
+    }
+}
+
+impl Order for Max {
+    fn precedes<Q>(&self, a: &Q, b: &Q) -> bool
+    where
+        Q: PartialOrd + ?Sized,
+    {
+        a > b
+    }
+}
+
+impl Order for Form {
+    fn precedes<Q>(&self, a: &Q, b: &Q) -> bool
+    where
+        Q: PartialOrd + ?Sized,
+    {
+        match self {
+            Form::Maximal => a > b,
+            Form::Minimal => a < b,
+        }
+    }
+}
+
+/// Key extraction policy
+pub trait KeyFn<T> {
+    /// `true` when `a` belongs closer to root than `b` under `order`
+    fn precedes<O>(&self, order: &O, a: &T, b: &T) -> bool
+    where
+        O: Order;
+}
+
+/// Items are compared directly.
+#[derive(Clone, Copy, Default)]
+pub struct Identity;
+
+/// Items are compared by `HeapKey::key`.
+#[derive(Clone, Copy, Default)]
+pub struct ByKey;
+
+/// Items are compared by key closure returns.
+pub struct FnKey<F>(pub F);
+
+/// Items are compared by `Comparator`.
+/// <br/>With `Max` order, `Ordering::Greater` items go to root,
+/// with `Min` order, `Ordering::Less` ones.
+#[derive(Clone, Copy, Default)]
+pub struct Cmp<F>(pub F);
+
+/// Heap order source for `Cmp`
+pub trait Comparator<T> {
+    fn order(&self, a: &T, b: &T) -> Ordering;
+}
+
+/// Incomparable items are seen as equal.
+/// <br/>`Minimal` reverses ordering, thus with `Max` order least item goes to root.
+impl<T> Comparator<T> for Form
+where
+    T: PartialOrd,
+{
+    fn order(&self, a: &T, b: &T) -> Ordering {
+        let ord = match self {
+            Form::Maximal => a.partial_cmp(b),
+            Form::Minimal => b.partial_cmp(a),
+        };
+
+        ord.unwrap_or(Ordering::Equal)
+    }
+}
+
+impl<T, F> Comparator<T> for F
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    fn order(&self, a: &T, b: &T) -> Ordering {
+        self(a, b)
+    }
+}
+
+pub trait HeapKey {
+    type Key: PartialOrd;
+    fn key(&self) -> Self::Key;
+}
+
+impl<T> KeyFn<T> for Identity
+where
+    T: PartialOrd,
+{
+    fn precedes<O>(&self, order: &O, a: &T, b: &T) -> bool
+    where
+        O: Order,
+    {
+        order.precedes(a, b)
+    }
+}
+
+impl<T> KeyFn<T> for ByKey
+where
+    T: HeapKey,
+{
+    fn precedes<O>(&self, order: &O, a: &T, b: &T) -> bool
+    where
+        O: Order,
+    {
+        order.precedes(&a.key(), &b.key())
+    }
+}
+
+impl<T, Q, F> KeyFn<T> for FnKey<F>
+where
+    F: Fn(&T) -> Q,
+    Q: PartialOrd,
+{
+    fn precedes<O>(&self, order: &O, a: &T, b: &T) -> bool
+    where
+        O: Order,
+    {
+        order.precedes(&(self.0)(a), &(self.0)(b))
+    }
+}
+
+impl<T, F> KeyFn<T> for Cmp<F>
+where
+    F: Comparator<T>,
+{
+    fn precedes<O>(&self, order: &O, a: &T, b: &T) -> bool
+    where
+        O: Order,
+    {
+        order.precedes(&self.0.order(a, b), &Ordering::Equal)
+    }
+}
+
+/// Item storage policy
+pub trait Storage<T> {
+    /// Room for `2^(levels + 1) - 1` items is made.
+    fn with_levels(levels: usize) -> Self;
+
+    /// `Fixed` capacity is that of `vec`.
+    fn from_vec(vec: Vec<T>) -> Self;
+
+    fn items(&self) -> &[T];
+
+    fn items_mut(&mut self) -> &mut [T];
+
+    /// Returns `t` back when storage is full.
+    fn push(&mut self, t: T) -> Result<(), T>;
+
+    fn pop(&mut self) -> Option<T>;
+
+    /// Item count storage holds without growth.
+    fn capacity(&self) -> usize;
+}
+
+/// Capacity is fixed on construction.
+pub struct Fixed<T> {
+    vec: Vec<T>,
+    cap: usize,
+}
+
+/// Capacity grows as needed, one level at time.
+/// <br/>Each growth doubles capacity (+1), i.e. `2^(levels+1)-1 ⇒ 2^(levels+2)-1`.
+/// Unlike `Fixed` growth is not limited by `MAX_LEVELS`.
+pub struct Growable<T> {
+    vec: Vec<T>,
+    cap: usize,
+}
+
+impl<T> Storage<T> for Fixed<T> {
+    fn with_levels(levels: usize) -> Self {
+        let cap = nodes(levels);
+
+        Fixed {
+            vec: Vec::with_capacity(cap),
+            cap,
+        }
+    }
+
+    fn from_vec(vec: Vec<T>) -> Self {
+        let cap = vec.capacity();
+        Fixed { vec, cap }
+    }
+
+    fn items(&self) -> &[T] {
+        &self.vec
+    }
+
+    fn items_mut(&mut self) -> &mut [T] {
+        &mut self.vec
+    }
+
+    fn push(&mut self, t: T) -> Result<(), T> {
+        if self.vec.len() == self.cap {
+            return Err(t);
+        }
+
+        self.vec.push(t);
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.vec.pop()
+    }
+
+    fn capacity(&self) -> usize {
+        self.cap
+    }
+}
+
+impl<T> Storage<T> for Growable<T> {
+    fn with_levels(levels: usize) -> Self {
+        let cap = nodes(levels);
+
+        Growable {
+            vec: Vec::with_capacity(cap),
+            cap,
+        }
+    }
+
+    /// Capacity is aligned to maximal leaf capacity of needed levels.
+    fn from_vec(vec: Vec<T>) -> Self {
+        let mut store = Growable { vec, cap: 0 };
+        store.resize(levels_for(store.vec.len()));
+        store
+    }
+
+    fn items(&self) -> &[T] {
+        &self.vec
+    }
+
+    fn items_mut(&mut self) -> &mut [T] {
+        &mut self.vec
+    }
+
+    /// Adds level when full.
+    fn push(&mut self, t: T) -> Result<(), T> {
+        if self.vec.len() == self.cap {
+            self.resize(levels_for(self.cap) + 1);
+        }
+
+        self.vec.push(t);
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.vec.pop()
+    }
+
+    fn capacity(&self) -> usize {
+        self.cap
+    }
+}
+
+impl<T> Growable<T> {
+    // `levels` must hold all items, unlike `nodes` not limited by `MAX_LEVELS`
+    fn resize(&mut self, levels: usize) {
+        let cap = 2usize.pow((levels + 1) as u32) - 1;
+
+        if cap > self.vec.capacity() {
+            self.vec.reserve_exact(cap - self.vec.len());
+        } else {
+            self.vec.shrink_to(cap);
+        }
+
+        self.cap = cap;
+    }
+}
+
+fn nodes(levels: usize) -> usize {
+    assert!(
+        levels <= MAX_LEVELS,
+        "Maximum supported levels is 25. 0 for root only."
+    );
+
+    2usize.pow((levels + 1) as u32) - 1
+}
+
+// least levels able to hold `len` nodes
+fn levels_for(len: usize) -> usize {
+    match len {
+        0 | 1 => 0,
+        _ => len.ilog2() as usize,
+    }
+}
+
+// levels needed for `len` items, leaf level is aligned to its maximal capacity
+fn levels(len: usize) -> usize {
+    let levels = match len {
+        0 | 1 => 0,
+        _ => (len - 1).ilog2() as usize + 1,
+    };
+
+    assert!(
+        levels <= MAX_LEVELS,
+        "Input length is greater than maximal heap item count support."
+    );
+
+    levels
+}
+
+/// Binary heap
+/// # Summary
+/// Items are moved by swaps, thus neither `Clone` nor `Default` is needed.
+/// <br/>`insert`, `extract_root`, `remove` TC: Ο(log n)
+/// <br/>`peek_root` TC: Ο(1)
+/// <br/>`from_vec_with`, `from_slice_with` TC: Ο(n)
+pub struct Heap<T, O = Min, K = Identity, S = Fixed<T>> {
+    store: S,
+    order: O,
+    key: K,
+    item: PhantomData<T>,
+}
+
+/// `Min` heap of fixed capacity
+pub type MinHeap<T> = Heap<T, Min, Identity, Fixed<T>>;
+
+/// `Max` heap of fixed capacity
+pub type MaxHeap<T> = Heap<T, Max, Identity, Fixed<T>>;
+
+impl<T, O, K, S> Heap<T, O, K, S>
+where
+    O: Order,
+    K: KeyFn<T>,
+    S: Storage<T>,
+{
+    pub fn with_policies(levels: usize, order: O, key: K) -> Self {
+        Heap {
+            store: S::with_levels(levels),
+            order,
+            key,
+            item: PhantomData,
+        }
+    }
+
+    /// Capacity is that of `vec` for `Fixed` storage.
+    pub fn from_vec_with(vec: Vec<T>, order: O, key: K) -> Self {
+        let mut heap = Heap {
+            store: S::from_vec(vec),
+            order,
+            key,
+            item: PhantomData,
+        };
+
+        heap.heapify();
+        heap
+    }
+
+    /// Capacity is aligned to maximal leaf level capacity.
+    pub fn from_slice_with(slice: &[T], order: O, key: K) -> Self
+    where
+        T: Clone,
+    {
+        let mut heap = Self::with_policies(levels(slice.len()), order, key);
+
+        for t in slice {
+            // capacity suffices
+            _ = heap.store.push(t.clone());
+        }
+
+        heap.heapify();
+        heap
+    }
+
+    /// Returns `Err(())` when storage is full.
+    pub fn insert(&mut self, t: T) -> Result<(), ()> {
+        if self.store.push(t).is_err() {
+            return Err(());
+        }
+
+        self.sift_up(self.len() - 1);
+        Ok(())
+    }
+
+    pub fn peek_root(&self) -> Option<&T> {
+        self.store.items().first()
+    }
+
+    pub fn extract_root(&mut self) -> Option<T> {
+        self.remove(0)
+    }
+
+    /// Removes item at level order index `ix`.
+    pub fn remove(&mut self, ix: usize) -> Option<T> {
+        let len = self.len();
+
+        if ix >= len {
+            return None;
+        }
+
+        self.store.items_mut().swap(ix, len - 1);
+        let item = self.store.pop();
+
+        if ix < len - 1 && self.sift_up(ix) == ix {
+            self.sift_down(ix);
+        }
+
+        item
+    }
+
+    pub fn len(&self) -> usize {
+        self.store.items().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.store.capacity()
+    }
+
+    /// Items in level order.
+    pub fn as_slice(&self) -> &[T] {
+        self.store.items()
+    }
+
+    // `a` belongs closer to root than `b`
+    fn precedes(&self, a: usize, b: usize) -> bool {
+        let items = self.store.items();
+        self.key.precedes(&self.order, &items[a], &items[b])
+    }
+
+    // returns final index
+    fn sift_up(&mut self, mut des_ix: usize) -> usize {
+        while des_ix > 0 {
+            let pred_ix = (des_ix - 1) / 2;
+
+            if !self.precedes(des_ix, pred_ix) {
+                break;
+            }
+
+            self.store.items_mut().swap(pred_ix, des_ix);
+            des_ix = pred_ix;
+        }
+
+        des_ix
+    }
+
+    fn sift_down(&mut self, mut pred_ix: usize) {
+        let len = self.len();
+
+        loop {
+            let mut des_ix = 2 * pred_ix + 1;
+
+            if des_ix >= len {
+                break;
+            }
+
+            let des2_ix = des_ix + 1;
+            if des2_ix < len && self.precedes(des2_ix, des_ix) {
+                des_ix = des2_ix;
+            }
+
+            if !self.precedes(des_ix, pred_ix) {
+                break;
+            }
+
+            self.store.items_mut().swap(pred_ix, des_ix);
+            pred_ix = des_ix;
+        }
+    }
+
+    // TC: Ο(n)
+    fn heapify(&mut self) {
+        for ix in (0..self.len() / 2).rev() {
+            self.sift_down(ix);
+        }
+    }
+}
+
+impl<T, O, K> Heap<T, O, K, Growable<T>>
+where
+    O: Order,
+    K: KeyFn<T>,
+{
+    /// 0 for root only.
+    pub fn levels(&self) -> usize {
+        levels_for(self.capacity())
+    }
+
+    /// Drops levels that are not needed for current length.
+    pub fn shrink_to_fit(&mut self) {
+        let levels = levels_for(self.len());
+        if levels < self.levels() {
+            self.store.resize(levels);
+        }
+    }
+}
+
+impl<T, K, S> Heap<T, Min, K, S>
+where
+    K: KeyFn<T>,
+    S: Storage<T>,
+{
+    pub fn peek_min(&self) -> Option<&T> {
+        self.peek_root()
+    }
+
+    pub fn extract_min(&mut self) -> Option<T> {
+        self.extract_root()
+    }
+}
+
+impl<T, K, S> Heap<T, Max, K, S>
+where
+    K: KeyFn<T>,
+    S: Storage<T>,
+{
+    pub fn peek_max(&self) -> Option<&T> {
+        self.peek_root()
+    }
+
+    pub fn extract_max(&mut self) -> Option<T> {
+        self.extract_root()
+    }
+}
+
+impl<T> Heap<T, Min, Identity, Fixed<T>>
+where
+    T: PartialOrd,
+{
+    pub fn new(levels: usize) -> Self {
+        Self::with_policies(levels, Min, Identity)
+    }
+}
+
+impl<T> Heap<T, Max, Identity, Fixed<T>>
+where
+    T: PartialOrd,
+{
+    pub fn new(levels: usize) -> Self {
+        Self::with_policies(levels, Max, Identity)
+    }
+}
+
+#[cfg(test)]
+mod tests_of_units {
+    use super::{
+        levels, ByKey, Cmp, FnKey, Form, Growable, Heap, HeapKey, Identity, KeyFn, Max, MaxHeap,
+        Min, MinHeap, Order, Storage,
+    };
+
+    fn valid<T, O, K, S>(heap: &Heap<T, O, K, S>) -> bool
+    where
+        O: Order,
+        K: KeyFn<T>,
+        S: Storage<T>,
+    {
+        (1..heap.len()).all(|ix| !heap.precedes(ix, (ix - 1) / 2))
+    }
+
+    fn drain<T, O, K, S>(heap: &mut Heap<T, O, K, S>) -> Vec<T>
+    where
+        O: Order,
+        K: KeyFn<T>,
+        S: Storage<T>,
+    {
+        let mut items = Vec::new();
+        while let Some(t) = heap.extract_root() {
+            items.push(t);
+        }
+
+        items
+    }
+
+    fn pseudo_random(len: usize) -> Vec<u32> {
+        let mut seed = 0x9e37_79b9_u32;
+        (0..len)
+            .map(|_| {
+                seed ^= seed << 13;
+                seed ^= seed >> 17;
+                seed ^= seed << 5;
+                seed % 100
+            })
+            .collect()
+    }
+
+    #[test]
+    fn orders() {
+        let data = [5, 1, 9, 3, 7];
+
+        let mut min = MinHeap::new(2);
+        let mut max = MaxHeap::new(2);
+        let mut form = Heap::<u8, Form>::with_policies(2, Form::Maximal, Identity);
+
+        for n in data {
+            assert_eq!(Ok(()), min.insert(n));
+            assert_eq!(Ok(()), max.insert(n));
+            assert_eq!(Ok(()), form.insert(n));
+        }
+
+        assert_eq!(Some(&1), min.peek_min());
+        assert_eq!(Some(&9), max.peek_max());
+
+        assert_eq!(vec![1, 3, 5, 7, 9], drain(&mut min));
+        assert_eq!(vec![9, 7, 5, 3, 1], drain(&mut max));
+        assert_eq!(vec![9, 7, 5, 3, 1], drain(&mut form));
+
+        assert_eq!(None, min.extract_min());
+        assert_eq!(None, max.peek_max());
+    }
+
+    #[test]
+    fn fixed_full() {
+        let mut heap = MinHeap::new(1);
+
+        for n in [3, 2, 1] {
+            assert_eq!(Ok(()), heap.insert(n));
+        }
+
+        assert_eq!(Err(()), heap.insert(0));
+        assert_eq!(3, heap.len());
+    }
+
+    #[test]
+    fn growable() {
+        let mut heap = Heap::<u32, Max, Identity, Growable<u32>>::with_policies(0, Max, Identity);
+
+        let data = pseudo_random(100);
+        for &n in data.iter() {
+            assert_eq!(Ok(()), heap.insert(n));
+        }
+
+        assert_eq!(100, heap.len());
+        assert!(valid(&heap));
+
+        let mut criterion = data;
+        criterion.sort_by(|a, b| b.cmp(a));
+        assert_eq!(criterion, drain(&mut heap));
+    }
+
+    #[test]
+    fn non_clone() {
+        #[derive(PartialEq, PartialOrd, Debug)]
+        struct Job(u8);
+
+        let mut heap = MinHeap::new(2);
+        for n in [4, 2, 8] {
+            _ = heap.insert(Job(n));
+        }
+
+        assert_eq!(Some(Job(2)), heap.extract_min());
+    }
+
+    #[test]
+    fn key_policies() {
+        struct Task {
+            prio: u8,
+            name: char,
+        }
+
+        impl HeapKey for Task {
+            type Key = u8;
+            fn key(&self) -> u8 {
+                self.prio
+            }
+        }
+
+        let tasks = || [(2, 'a'), (1, 'b'), (3, 'c')].map(|(prio, name)| Task { prio, name });
+
+        let mut by_key: Heap<_, _, _> = Heap::with_policies(1, Min, ByKey);
+        let mut fn_key: Heap<_, _, _> = Heap::with_policies(1, Max, FnKey(|t: &Task| t.prio));
+        let mut cmp: Heap<_, _, _> =
+            Heap::with_policies(1, Min, Cmp(|a: &Task, b: &Task| a.name.cmp(&b.name)));
+
+        for (a, (b, c)) in tasks().into_iter().zip(tasks().into_iter().zip(tasks())) {
+            _ = by_key.insert(a);
+            _ = fn_key.insert(b);
+            _ = cmp.insert(c);
+        }
+
+        let names = |tasks: Vec<Task>| tasks.iter().map(|t| t.name).collect::<String>();
+
+        assert_eq!("bac", names(drain(&mut by_key)));
+        assert_eq!("cab", names(drain(&mut fn_key)));
+        assert_eq!("abc", names(drain(&mut cmp)));
+    }
+
+    #[test]
+    fn cmp_max_order() {
+        let mut heap: Heap<_, _, _> = Heap::with_policies(2, Max, Cmp(|a: &i8, b: &i8| b.cmp(a)));
+
+        for n in [3, -1, 7, 0] {
+            _ = heap.insert(n);
+        }
+
+        assert_eq!(Some(-1), heap.extract_max());
+    }
+
+    #[test]
+    fn remove() {
+        let data = pseudo_random(60);
+
+        for ix in 0..60 {
+            let mut heap = Heap::<u32>::from_vec_with(data.clone(), Min, Identity);
+            assert!(valid(&heap));
+
+            let removed = heap.as_slice()[ix];
+            assert_eq!(Some(removed), heap.remove(ix));
+            assert!(valid(&heap));
+
+            let mut criterion = data.clone();
+            let pos = criterion.iter().position(|n| *n == removed).unwrap();
+            criterion.remove(pos);
+            criterion.sort();
+
+            assert_eq!(criterion, drain(&mut heap));
+        }
+
+        let mut heap = MinHeap::<u8>::new(0);
+        assert_eq!(None, heap.remove(0));
+    }
+
+    #[test]
+    fn from_slice_with() {
+        let data = pseudo_random(9);
+        let heap = Heap::<u32>::from_slice_with(&data, Min, Identity);
+
+        assert!(valid(&heap));
+        assert_eq!(9, heap.len());
+        assert_eq!(Some(data.iter().min().unwrap()), heap.peek_root());
+    }
+
+    #[test]
+    fn from_vec_capacity() {
+        let mut vec = Vec::with_capacity(4);
+        vec.extend([3, 1]);
+
+        let mut heap = Heap::<u8>::from_vec_with(vec, Min, Identity);
+        assert_eq!(Ok(()), heap.insert(2));
+        assert_eq!(Ok(()), heap.insert(0));
+        assert_eq!(Err(()), heap.insert(5));
+    }
+
+    #[test]
+    fn levels_test() {
+        #[rustfmt::skip]
+        let test_cases = [(0, 0), (1, 0), (2, 1), (3, 2), (4, 2), (5, 3), (8, 3), (9, 4)];
+
+        for (len, criterion) in test_cases {
+            assert_eq!(criterion, levels(len), "{}", len);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Maximum supported levels is 25. 0 for root only.")]
+    fn unsupported_levels() {
+        _ = MinHeap::<u8>::new(26);
+    }
+}
@@ -0,0 +1,288 @@
+//! Stable tie-breaking
+//!
+//! Items equal by heap order are ranked by insertion counter, `Ties` selects
+//! whether first or last inserted one is extracted first. When counter is
+//! exhausted, ranks of present items are renumbered keeping their relative order.
+
+use super::{Fixed, Heap, Identity, KeyFn, Max, Min, Order, Storage};
+
+/// Order of items equal by heap order
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Ties {
+    /// first inserted, first extracted
+    Fifo,
+    /// last inserted, first extracted
+    Lifo,
+}
+
+/// Item ranked by insertion
+pub struct Ranked<T> {
+    item: T,
+    // lesser tie precedes
+    tie: u64,
+}
+
+/// Key policy `K` with equal items ordered by rank
+pub struct Ranking<K>(K);
+
+impl<T, K> KeyFn<Ranked<T>> for Ranking<K>
+where
+    K: KeyFn<T>,
+{
+    fn precedes<O>(&self, order: &O, a: &Ranked<T>, b: &Ranked<T>) -> bool
+    where
+        O: Order,
+    {
+        if self.0.precedes(order, &a.item, &b.item) {
+            return true;
+        }
+
+        if self.0.precedes(order, &b.item, &a.item) {
+            return false;
+        }
+
+        a.tie < b.tie
+    }
+}
+
+/// `Heap` extracting equal items in insertion order or reversed
+pub struct StableHeap<T, O = Min, K = Identity, S = Fixed<Ranked<T>>> {
+    heap: Heap<Ranked<T>, O, Ranking<K>, S>,
+    ties: Ties,
+    // insertion counter
+    counter: u64,
+}
+
+impl<T, O, K, S> StableHeap<T, O, K, S>
+where
+    O: Order,
+    K: KeyFn<T>,
+    S: Storage<Ranked<T>>,
+{
+    pub fn with_policies(levels: usize, order: O, key: K, ties: Ties) -> Self {
+        StableHeap {
+            heap: Heap::with_policies(levels, order, Ranking(key)),
+            ties,
+            counter: 0,
+        }
+    }
+
+    /// Returns `Err(())` when storage is full.
+    pub fn insert(&mut self, t: T) -> Result<(), ()> {
+        if self.counter == u64::MAX {
+            let live = self
+                .heap
+                .store
+                .items_mut()
+                .iter_mut()
+                .map(|r| &mut r.tie)
+                .collect();
+
+            self.counter = renumber(self.ties, live);
+        }
+
+        let tie = tie(self.ties, self.counter);
+        self.heap.insert(Ranked { item: t, tie })?;
+
+        self.counter += 1;
+        Ok(())
+    }
+
+    pub fn peek_root(&self) -> Option<&T> {
+        self.heap.peek_root().map(|r| &r.item)
+    }
+
+    pub fn extract_root(&mut self) -> Option<T> {
+        self.heap.extract_root().map(|r| r.item)
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+}
+
+impl<T, K, S> StableHeap<T, Min, K, S>
+where
+    K: KeyFn<T>,
+    S: Storage<Ranked<T>>,
+{
+    pub fn peek_min(&self) -> Option<&T> {
+        self.peek_root()
+    }
+
+    pub fn extract_min(&mut self) -> Option<T> {
+        self.extract_root()
+    }
+}
+
+impl<T, K, S> StableHeap<T, Max, K, S>
+where
+    K: KeyFn<T>,
+    S: Storage<Ranked<T>>,
+{
+    pub fn peek_max(&self) -> Option<&T> {
+        self.peek_root()
+    }
+
+    pub fn extract_max(&mut self) -> Option<T> {
+        self.extract_root()
+    }
+}
+
+/// Rank of item inserted as `counter`-th.
+pub fn tie(ties: Ties, counter: u64) -> u64 {
+    match ties {
+        Ties::Fifo => counter,
+        Ties::Lifo => u64::MAX - counter,
+    }
+}
+
+/// Compacts `live` ranks to lowest counters keeping their relative order.
+/// <br/>Returns next insertion counter.
+pub fn renumber(ties: Ties, mut live: Vec<&mut u64>) -> u64 {
+    live.sort_by_key(|t| **t);
+
+    let len = live.len() as u64;
+    for (ix, t) in live.into_iter().enumerate() {
+        let counter = match ties {
+            Ties::Fifo => ix as u64,
+            Ties::Lifo => len - 1 - ix as u64,
+        };
+
+        *t = tie(ties, counter);
+    }
+
+    len
+}
+
+#[cfg(test)]
+mod tests_of_units {
+    use super::{renumber, Ranked, StableHeap, Ties};
+    use crate::{Cmp, FnKey, Form, Growable, Identity, KeyFn, Max, Min, Order, Storage};
+
+    // equal by priority, distinct by name
+    fn jobs() -> [(u8, char); 7] {
+        [
+            (2, 'a'),
+            (1, 'b'),
+            (2, 'c'),
+            (3, 'd'),
+            (2, 'e'),
+            (1, 'f'),
+            (2, 'g'),
+        ]
+    }
+
+    fn drain<O, K, S>(heap: &mut StableHeap<(u8, char), O, K, S>) -> String
+    where
+        O: Order,
+        K: KeyFn<(u8, char)>,
+        S: Storage<Ranked<(u8, char)>>,
+    {
+        let mut names = String::new();
+        while let Some(job) = heap.extract_root() {
+            names.push(job.1);
+        }
+
+        names
+    }
+
+    fn prio(job: &(u8, char)) -> u8 {
+        job.0
+    }
+
+    #[test]
+    fn ties() {
+        #[rustfmt::skip]
+        let test_cases = [
+            (Form::Minimal, Ties::Fifo, "bfacegd"),
+            (Form::Minimal, Ties::Lifo, "fbgecad"),
+            (Form::Maximal, Ties::Fifo, "dacegbf"),
+            (Form::Maximal, Ties::Lifo, "dgecafb"),
+        ];
+
+        for (form, ties, criterion) in test_cases {
+            let mut heap: StableHeap<_, _, _> =
+                StableHeap::with_policies(3, form, FnKey(prio), ties);
+            for job in jobs() {
+                assert_eq!(Ok(()), heap.insert(job));
+            }
+
+            assert_eq!(7, heap.len());
+            assert_eq!(criterion, drain(&mut heap), "{:?} {:?}", form, ties);
+        }
+    }
+
+    #[test]
+    fn comparator() {
+        let by_prio = |a: &(u8, char), b: &(u8, char)| b.0.cmp(&a.0);
+
+        let mut heap: StableHeap<_, _, _> =
+            StableHeap::with_policies(3, Max, Cmp(by_prio), Ties::Fifo);
+        for job in jobs() {
+            _ = heap.insert(job);
+        }
+
+        assert_eq!(Some(&(1, 'b')), heap.peek_max());
+        assert_eq!("bfacegd", drain(&mut heap));
+    }
+
+    #[test]
+    fn full_error() {
+        let mut heap: StableHeap<_> = StableHeap::with_policies(0, Min, Identity, Ties::Lifo);
+        assert_eq!(Ok(()), heap.insert(1));
+        assert_eq!(Err(()), heap.insert(2));
+
+        // failed insertion does not consume counter
+        assert_eq!(1, heap.counter);
+        assert_eq!(Some(1), heap.extract_min());
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn growable() {
+        let mut heap =
+            StableHeap::<_, _, _, Growable<_>>::with_policies(0, Min, FnKey(prio), Ties::Fifo);
+        for job in jobs() {
+            assert_eq!(Ok(()), heap.insert(job));
+        }
+
+        assert_eq!("bfacegd", drain(&mut heap));
+    }
+
+    #[test]
+    fn counter_overflow() {
+        for (ties, criterion) in [(Ties::Fifo, "acegbdf"), (Ties::Lifo, "gecafdb")] {
+            let mut heap: StableHeap<_, _, _> =
+                StableHeap::with_policies(3, Min, FnKey(prio), ties);
+            heap.counter = u64::MAX - 3;
+
+            for (ix, name) in "abcdefg".chars().enumerate() {
+                _ = heap.insert((ix as u8 % 2, name));
+            }
+
+            // 3 insertions before exhaustion, 4 after renumbering
+            assert_eq!(7, heap.counter);
+            assert_eq!(criterion, drain(&mut heap));
+        }
+    }
+
+    #[test]
+    fn renumber_test() {
+        let mut ties = [u64::MAX - 2, 5, u64::MAX - 7];
+        let [a, b, c] = &mut ties;
+
+        assert_eq!(3, renumber(Ties::Fifo, vec![a, b, c]));
+        assert_eq!([2, 0, 1], ties);
+
+        let mut ties = [u64::MAX - 2, 5, u64::MAX - 7];
+        let [a, b, c] = &mut ties;
+
+        assert_eq!(3, renumber(Ties::Lifo, vec![a, b, c]));
+        assert_eq!([u64::MAX, u64::MAX - 2, u64::MAX - 1], ties);
+    }
+}
@@ -0,0 +1,389 @@
+//! Priority aging against starvation
+//!
+//! Effective priority of waiting item moves towards root by boost
+//! `Aging` maps its waiting to. Waiting is measured in `AgeUnit`s.
+
+use super::{MinQueue, Priority, PriorityQueue, QueueForm};
+use std::cmp::Ordering;
+use std::time::{Duration, Instant};
+
+/// Aging function, maps waiting to priority boost
+/// <br/>Boost must not decrease as waiting grows.
+pub trait Aging {
+    fn boost(&self, waited: u64) -> u64;
+
+    /// `false` when `boost` is constantly 0
+    fn ages(&self) -> bool {
+        true
+    }
+}
+
+/// `boost = waited * rate`, `Linear(rate)`
+pub struct Linear(pub u64);
+
+/// `boost = waited / every * step`
+pub struct Stepped {
+    every: u64,
+    step: u64,
+}
+
+/// `boost = 2^(waited / every) - 1`, saturating
+pub struct Exponential {
+    every: u64,
+}
+
+impl Stepped {
+    pub fn new(every: u64, step: u64) -> Self {
+        assert!(every > 0, "Aging period must be positive.");
+        Stepped { every, step }
+    }
+}
+
+impl Exponential {
+    pub fn new(every: u64) -> Self {
+        assert!(every > 0, "Aging period must be positive.");
+        Exponential { every }
+    }
+}
+
+impl Aging for Linear {
+    fn boost(&self, waited: u64) -> u64 {
+        waited.saturating_mul(self.0)
+    }
+}
+
+impl Aging for Stepped {
+    fn boost(&self, waited: u64) -> u64 {
+        (waited / self.every).saturating_mul(self.step)
+    }
+}
+
+impl Aging for Exponential {
+    fn boost(&self, waited: u64) -> u64 {
+        let exp = waited / self.every;
+
+        if exp >= u64::BITS as u64 {
+            u64::MAX
+        } else {
+            (1 << exp) - 1
+        }
+    }
+}
+
+impl<F> Aging for F
+where
+    F: Fn(u64) -> u64,
+{
+    fn boost(&self, waited: u64) -> u64 {
+        self(waited)
+    }
+}
+
+/// Waiting measure
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum AgeUnit {
+    /// extractions since insertion
+    Dequeues,
+    /// whole time units since insertion
+    Time(Duration),
+}
+
+/// Waiting of extracted items
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct WaitStats {
+    pub dequeues: u64,
+    /// maximal extractions item waited through
+    pub max_dequeues: u64,
+    /// maximal time item waited
+    pub max_time: Duration,
+}
+
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+struct Stamp {
+    dequeues: u64,
+    // since queue epoch
+    since: Duration,
+}
+
+#[derive(Clone, Default, Debug, PartialEq)]
+struct Aged<T> {
+    item: T,
+    base: u64,
+    eff: i128,
+    stamp: Stamp,
+}
+
+impl<T> Priority for Aged<T> {
+    type Priority = i128;
+    fn prio(&self) -> i128 {
+        self.eff
+    }
+}
+
+/// Priority queue with aging
+/// # Summary
+/// Effective priorities are recomputed ahead of every `refresh`-th extraction,
+/// right after preceding one, in between they stay as computed last time.
+/// So root `peek_root` sees is always one `extract_root` takes.
+/// <br/>`insert` TC: Ο(log n)
+/// <br/>`extract_root` TC: Ο(log n), with recomputation Ο(n)
+pub struct AgingQueue<T, A, F = MinQueue>
+where
+    T: Clone + Default,
+    F: QueueForm,
+{
+    queue: PriorityQueue<Aged<T>, F>,
+    aging: A,
+    unit: AgeUnit,
+    epoch: Instant,
+    stats: WaitStats,
+    refresh: u64,
+    // extractions since recomputation, modulo `refresh`
+    extracted: u64,
+}
+
+impl<T, A, F> AgingQueue<T, A, F>
+where
+    T: Clone + Default,
+    A: Aging,
+    F: QueueForm,
+{
+    /// `refresh` of 1 keeps order exact for `AgeUnit::Dequeues`.
+    pub fn new(levels: usize, aging: A, unit: AgeUnit, refresh: u64) -> Self {
+        assert!(refresh > 0, "Refresh period must be positive.");
+
+        Self {
+            queue: PriorityQueue::new(levels),
+            aging,
+            unit,
+            epoch: Instant::now(),
+            stats: WaitStats::default(),
+            refresh,
+            extracted: 0,
+        }
+    }
+
+    /// `prio` is base priority.
+    pub fn insert(&mut self, t: T, prio: u64) -> Result<(), ()> {
+        let mut aged = Aged {
+            item: t,
+            base: prio,
+            eff: 0,
+            stamp: Stamp {
+                dequeues: self.stats.dequeues,
+                since: self.epoch.elapsed(),
+            },
+        };
+
+        aged.eff = self.effective(&aged, self.epoch.elapsed());
+        self.queue.insert(aged)
+    }
+
+    pub fn peek_root(&self) -> Option<&T> {
+        self.queue.peek_root().map(|a| &a.item)
+    }
+
+    pub fn extract_root(&mut self) -> Option<T> {
+        let aged = self.queue.extract_root()?;
+
+        let stats = &mut self.stats;
+        let stamp = aged.stamp;
+
+        stats.max_dequeues = stats.max_dequeues.max(stats.dequeues - stamp.dequeues);
+        stats.max_time = stats.max_time.max(self.epoch.elapsed() - stamp.since);
+        stats.dequeues += 1;
+
+        self.extracted = (self.extracted + 1) % self.refresh;
+        if self.extracted == self.refresh - 1 {
+            self.recompute();
+        }
+
+        Some(aged.item)
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Waiting of extracted items.
+    pub fn stats(&self) -> &WaitStats {
+        &self.stats
+    }
+
+    // `since` — elapsed since epoch
+    fn effective(&self, aged: &Aged<T>, since: Duration) -> i128 {
+        let waited = match self.unit {
+            AgeUnit::Dequeues => self.stats.dequeues - aged.stamp.dequeues,
+            AgeUnit::Time(unit) => {
+                let waited = since.saturating_sub(aged.stamp.since);
+                (waited.as_nanos() / unit.as_nanos().max(1)) as u64
+            }
+        };
+
+        let base = aged.base as i128;
+        let boost = self.aging.boost(waited) as i128;
+
+        // boost moves towards root
+        if F::precedes(Ordering::Less) {
+            base - boost
+        } else {
+            base + boost
+        }
+    }
+
+    // TC: Ο(n)
+    fn recompute(&mut self) {
+        let since = self.epoch.elapsed();
+        let len = self.queue.len;
+
+        for ix in 0..len {
+            let eff = self.effective(&self.queue.data[ix].item, since);
+            self.queue.data[ix].item.eff = eff;
+        }
+
+        for ix in (0..len / 2).rev() {
+            self.queue.bubble_down(ix);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_of_units {
+    use super::{AgeUnit, Aging, AgingQueue, Exponential, Linear, Stepped};
+    use crate::{MaxQueue, QueueForm};
+    use std::time::Duration;
+
+    // steady load of items closest to root, 1 item in, 1 item out
+    fn load<A, F>(queue: &mut AgingQueue<u8, A, F>, prio: u64, rounds: usize) -> Option<usize>
+    where
+        A: Aging,
+        F: QueueForm,
+    {
+        for round in 0..rounds {
+            _ = queue.insert(1, prio);
+
+            if queue.extract_root() == Some(9) {
+                return Some(round);
+            }
+        }
+
+        None
+    }
+
+    #[test]
+    fn functions() {
+        #[rustfmt::skip]
+        let test_cases: [(&dyn Aging, [u64; 4]); 4] = [
+            (&Linear(3), [0, 3, 6, 30]),
+            (&Stepped::new(2, 5), [0, 0, 5, 25]),
+            (&Exponential::new(1), [0, 1, 3, 1023]),
+            (&|w: u64| w * w, [0, 1, 4, 100]),
+        ];
+
+        for (ix, (aging, criterion)) in test_cases.into_iter().enumerate() {
+            let boosts = [0, 1, 2, 10].map(|w| aging.boost(w));
+            assert_eq!(criterion, boosts, "{}", ix);
+        }
+
+        assert_eq!(u64::MAX, Exponential::new(1).boost(64));
+    }
+
+    #[test]
+    #[should_panic(expected = "Aging period must be positive.")]
+    fn zero_step_period() {
+        _ = Stepped::new(0, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Aging period must be positive.")]
+    fn zero_exponential_period() {
+        _ = Exponential::new(0);
+    }
+
+    #[test]
+    fn no_boost_starves() {
+        let mut queue = AgingQueue::<u8, _>::new(2, |_| 0, AgeUnit::Dequeues, 1);
+        _ = queue.insert(9, 10);
+
+        assert_eq!(None, load(&mut queue, 0, 100));
+        assert_eq!(0, queue.stats().max_dequeues);
+        assert_eq!(1, queue.len());
+    }
+
+    #[test]
+    fn min_queue() {
+        let mut queue = AgingQueue::<u8, _>::new(2, Linear(1), AgeUnit::Dequeues, 1);
+        _ = queue.insert(9, 10);
+
+        // on tie earlier inserted item wins
+        assert_eq!(Some(10), load(&mut queue, 0, 100));
+        assert_eq!(10, queue.stats().max_dequeues);
+        assert_eq!(11, queue.stats().dequeues);
+
+        // item inserted in last round
+        assert_eq!(Some(1), queue.extract_root());
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn max_queue() {
+        let mut queue =
+            AgingQueue::<u8, _, MaxQueue>::new(2, Stepped::new(2, 3), AgeUnit::Dequeues, 1);
+        _ = queue.insert(9, 0);
+
+        assert_eq!(Some(6), load(&mut queue, 9, 100));
+    }
+
+    #[test]
+    fn peek_matches_extract() {
+        let mut queue = AgingQueue::<u8, _>::new(2, Linear(10), AgeUnit::Dequeues, 1);
+        _ = queue.insert(9, 10);
+        _ = queue.insert(1, 0);
+
+        assert_eq!(Some(1), queue.extract_root());
+        _ = queue.insert(2, 5);
+
+        assert_eq!(Some(&9), queue.peek_root());
+        assert_eq!(Some(9), queue.extract_root());
+        assert_eq!(Some(&2), queue.peek_root());
+        assert_eq!(Some(2), queue.extract_root());
+    }
+
+    #[test]
+    fn refresh_period() {
+        let mut queue = AgingQueue::<u8, _>::new(2, Linear(1), AgeUnit::Dequeues, 4);
+        _ = queue.insert(9, 10);
+
+        // recomputations happen before 4th, 8th, 12th extraction
+        assert_eq!(Some(11), load(&mut queue, 0, 100));
+    }
+
+    #[test]
+    fn time_unit() {
+        let unit = AgeUnit::Time(Duration::from_secs(3600));
+        let mut queue = AgingQueue::<u8, _>::new(2, Linear(1), unit, 1);
+        _ = queue.insert(9, 10);
+
+        assert_eq!(None, load(&mut queue, 0, 20));
+        assert!(queue.stats().max_time < Duration::from_secs(3600));
+    }
+
+    #[test]
+    fn full_queue() {
+        let mut queue = AgingQueue::<u8, _>::new(0, Linear(1), AgeUnit::Dequeues, 1);
+
+        assert_eq!(Ok(()), queue.insert(1, 1));
+        assert_eq!(Err(()), queue.insert(2, 0));
+        assert_eq!(Some(&1), queue.peek_root());
+    }
+
+    #[test]
+    #[should_panic(expected = "Refresh period must be positive.")]
+    fn zero_refresh() {
+        _ = AgingQueue::<u8, _>::new(0, Linear(1), AgeUnit::Dequeues, 0);
+    }
+}
@@ -0,0 +1,490 @@
+//! Thread-safe priority queues over `PriorityQueue`
+//!
+//! - `SyncQueue` — one `PriorityQueue` behind mutex, exact order,
+//!   blocking waits on empty or full queue
+//! - `MultiQueue` — several `PriorityQueue`s each behind own mutex,
+//!   `pop` chooses better root of two randomly picked queues thus extraction
+//!   order is only approximate, contention is spread among queues
+
+use super::{MinQueue, Priority, PriorityQueue, QueueForm, Ties};
+use std::cell::Cell;
+use std::sync::{Condvar, Mutex, MutexGuard, TryLockError};
+use std::time::{Duration, Instant};
+
+/// Lock-based priority queue
+/// # Summary
+/// Capacity is fixed by `levels` as with `PriorityQueue`. `push` refuses
+/// item on full queue with `Err(())` so that producers can back off,
+/// `push_wait` blocks until space is available or timeout elapses.
+pub struct SyncQueue<T, F = MinQueue>
+where
+    T: Clone + Default + Priority,
+    F: QueueForm,
+{
+    queue: Mutex<PriorityQueue<T, F>>,
+    not_empty: Condvar,
+    not_full: Condvar,
+}
+
+impl<T, F> SyncQueue<T, F>
+where
+    T: Clone + Default + Priority,
+    F: QueueForm,
+{
+    pub fn new(levels: usize) -> Self {
+        Self::with_ties(levels, Ties::Fifo)
+    }
+
+    pub fn with_ties(levels: usize, ties: Ties) -> Self {
+        SyncQueue {
+            queue: Mutex::new(PriorityQueue::with_ties(levels, ties)),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+        }
+    }
+
+    /// Returns `Err(())` when queue is full.
+    pub fn push(&self, t: T) -> Result<(), ()> {
+        let res = self.lock().insert(t);
+
+        if res.is_ok() {
+            self.not_empty.notify_one();
+        }
+
+        res
+    }
+
+    /// Waits at most `timeout` for space in queue.
+    /// Returns `Err(())` when queue is still full.
+    pub fn push_wait(&self, t: T, timeout: Duration) -> Result<(), ()> {
+        let queue = self.lock();
+        let mut queue = wait_while(&self.not_full, queue, timeout, |q| q.len == q.data.len());
+
+        let res = queue.insert(t);
+        drop(queue);
+
+        if res.is_ok() {
+            self.not_empty.notify_one();
+        }
+
+        res
+    }
+
+    pub fn pop(&self) -> Option<T> {
+        let root = self.lock().extract_root();
+
+        if root.is_some() {
+            self.not_full.notify_one();
+        }
+
+        root
+    }
+
+    /// Waits at most `timeout` for item.
+    /// Returns `None` when queue is still empty.
+    pub fn pop_wait(&self, timeout: Duration) -> Option<T> {
+        let queue = self.lock();
+        let mut queue = wait_while(&self.not_empty, queue, timeout, |q| q.len == 0);
+
+        let root = queue.extract_root();
+        drop(queue);
+
+        if root.is_some() {
+            self.not_full.notify_one();
+        }
+
+        root
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> MutexGuard<'_, PriorityQueue<T, F>> {
+        // poisoned lock is recovered, panicking `T::prio`
+        // can leave queue only out of order
+        self.queue.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn wait_while<'a, Q>(
+    cvar: &Condvar,
+    mut guard: MutexGuard<'a, Q>,
+    timeout: Duration,
+    cond: impl Fn(&Q) -> bool,
+) -> MutexGuard<'a, Q> {
+    let deadline = Instant::now() + timeout;
+
+    while cond(&guard) {
+        let now = Instant::now();
+        if now >= deadline {
+            break;
+        }
+
+        guard = match cvar.wait_timeout(guard, deadline - now) {
+            Ok((g, _)) => g,
+            Err(e) => e.into_inner().0,
+        };
+    }
+
+    guard
+}
+
+/// Relaxed multi-queue
+/// # Summary
+/// `push` inserts into randomly picked queue, `pop` extracts from better
+/// of two randomly picked queues. Item extracted is thus not guaranteed
+/// to be root of all items, rather one of roots close to it.
+/// <br/>
+/// Total capacity is `queues` times capacity given by `levels`.
+/// When picked queues are full or empty, `push` and `pop` sweep all queues
+/// one by one. Sweep is best-effort: queues are locked one at time, thus while
+/// other threads operate `push` can fail with `Err(())` though some queue
+/// has free slot and `pop` can return `None` though some queue holds item.
+pub struct MultiQueue<T, F = MinQueue>
+where
+    T: Clone + Default + Priority,
+    F: QueueForm,
+{
+    queues: Box<[Mutex<PriorityQueue<T, F>>]>,
+}
+
+impl<T, F> MultiQueue<T, F>
+where
+    T: Clone + Default + Priority,
+    F: QueueForm,
+{
+    /// `queues` is commonly set to twice the count of working threads.
+    pub fn new(queues: usize, levels: usize) -> Self {
+        assert!(queues > 0, "Queue count must be positive.");
+
+        let queues = (0..queues)
+            .map(|_| Mutex::new(PriorityQueue::new(levels)))
+            .collect();
+
+        MultiQueue { queues }
+    }
+
+    pub fn push(&self, t: T) -> Result<(), ()> {
+        let count = self.queues.len();
+        let start = random(count);
+
+        // contended queues are skipped on first pass, awaited on second one
+        for blocking in [false, true] {
+            for offset in 0..count {
+                let mutex = &self.queues[(start + offset) % count];
+
+                let mut queue = if blocking {
+                    lock(mutex)
+                } else {
+                    match mutex.try_lock() {
+                        Ok(q) => q,
+                        Err(TryLockError::Poisoned(e)) => e.into_inner(),
+                        Err(TryLockError::WouldBlock) => continue,
+                    }
+                };
+
+                if queue.len < queue.data.len() {
+                    return queue.insert(t);
+                }
+            }
+        }
+
+        Err(())
+    }
+
+    pub fn pop(&self) -> Option<T> {
+        let count = self.queues.len();
+
+        let a = random(count);
+        let b = random(count);
+
+        if a != b {
+            // locks are taken in index order to avoid deadlock
+            let (a, b) = if a < b { (a, b) } else { (b, a) };
+
+            let mut qa = lock(&self.queues[a]);
+            let mut qb = lock(&self.queues[b]);
+
+            let take_b = match (qa.peek_root(), qb.peek_root()) {
+                (Some(ra), Some(rb)) => Some(F::precedes(rb.prio().cmp(&ra.prio()))),
+                (Some(_), None) => Some(false),
+                (None, Some(_)) => Some(true),
+                (None, None) => None,
+            };
+
+            return match take_b {
+                Some(true) => qb.extract_root(),
+                Some(false) => qa.extract_root(),
+                None => {
+                    drop(qa);
+                    drop(qb);
+                    self.pop_any()
+                }
+            };
+        }
+
+        let root = lock(&self.queues[a]).extract_root();
+
+        match root {
+            None => self.pop_any(),
+            root => root,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.queues.iter().map(|q| lock(q).len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // sweeps all queues, `None` means each was empty when locked
+    fn pop_any(&self) -> Option<T> {
+        self.queues.iter().find_map(|q| lock(q).extract_root())
+    }
+}
+
+fn lock<Q>(mutex: &Mutex<Q>) -> MutexGuard<'_, Q> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+thread_local! {
+    static SEED: Cell<u64> = Cell::new(seed());
+}
+
+fn seed() -> u64 {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+
+    // `RandomState` is randomly keyed per thread
+    RandomState::new().build_hasher().finish() | 1
+}
+
+// xorshift64
+fn random(bound: usize) -> usize {
+    SEED.with(|s| {
+        let mut x = s.get();
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        s.set(x);
+
+        (x % bound as u64) as usize
+    })
+}
+
+#[cfg(test)]
+mod tests_of_units {
+    use super::{MultiQueue, SyncQueue};
+    use crate::{MaxQueue, Prio};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    const THREADS: usize = 4;
+    const PER_THREAD: usize = 2_000;
+
+    #[test]
+    fn sync_push_pop() {
+        let queue = SyncQueue::<u16, MaxQueue>::new(1);
+
+        for n in [3, 9, 1] {
+            assert_eq!(Ok(()), queue.push(n));
+        }
+
+        assert_eq!(Err(()), queue.push(5));
+        assert_eq!(3, queue.len());
+
+        assert_eq!(Some(9), queue.pop());
+        assert_eq!(Some(3), queue.pop());
+        assert_eq!(Some(1), queue.pop());
+        assert_eq!(None, queue.pop());
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn pop_wait_timeout() {
+        let queue = SyncQueue::<u16>::new(1);
+
+        let start = Instant::now();
+        assert_eq!(None, queue.pop_wait(Duration::from_millis(20)));
+        assert!(start.elapsed() >= Duration::from_millis(20));
+    }
+
+    #[test]
+    fn push_wait_timeout() {
+        let queue = SyncQueue::<u16>::new(0);
+        assert_eq!(Ok(()), queue.push(1));
+
+        let start = Instant::now();
+        assert_eq!(Err(()), queue.push_wait(2, Duration::from_millis(20)));
+        assert!(start.elapsed() >= Duration::from_millis(20));
+    }
+
+    #[test]
+    fn pop_wait_wakes() {
+        let queue = SyncQueue::<u16>::new(1);
+
+        thread::scope(|s| {
+            let consumer = s.spawn(|| queue.pop_wait(Duration::from_secs(10)));
+
+            thread::sleep(Duration::from_millis(20));
+            assert_eq!(Ok(()), queue.push(7));
+
+            assert_eq!(Some(7), consumer.join().unwrap());
+        });
+    }
+
+    #[test]
+    fn push_wait_wakes() {
+        let queue = SyncQueue::<u16>::new(0);
+        assert_eq!(Ok(()), queue.push(1));
+
+        thread::scope(|s| {
+            let producer = s.spawn(|| queue.push_wait(2, Duration::from_secs(10)));
+
+            thread::sleep(Duration::from_millis(20));
+            assert_eq!(Some(1), queue.pop());
+
+            assert_eq!(Ok(()), producer.join().unwrap());
+            assert_eq!(Some(2), queue.pop());
+        });
+    }
+
+    // each item is extracted exactly once
+    fn check_seen(mut seen: Vec<usize>) {
+        seen.sort_unstable();
+        assert_eq!((0..THREADS * PER_THREAD).collect::<Vec<usize>>(), seen);
+    }
+
+    #[test]
+    fn sync_stress() {
+        // small capacity forces producers to wait
+        let queue = SyncQueue::<Prio<usize, usize>>::new(4);
+        let timeout = Duration::from_secs(10);
+
+        let seen = thread::scope(|s| {
+            for t in 0..THREADS {
+                let queue = &queue;
+                s.spawn(move || {
+                    for ix in 0..PER_THREAD {
+                        let n = t * PER_THREAD + ix;
+                        assert_eq!(Ok(()), queue.push_wait(Prio(n % 97, n), timeout));
+                    }
+                });
+            }
+
+            let consumers = (0..THREADS)
+                .map(|_| {
+                    s.spawn(|| {
+                        let mut seen = Vec::new();
+                        for _ in 0..PER_THREAD {
+                            seen.push(queue.pop_wait(timeout).unwrap().1);
+                        }
+                        seen
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            consumers
+                .into_iter()
+                .flat_map(|c| c.join().unwrap())
+                .collect::<Vec<usize>>()
+        });
+
+        assert!(queue.is_empty());
+        check_seen(seen);
+    }
+
+    #[test]
+    fn sync_order_after_producers() {
+        let queue = SyncQueue::<Prio<usize, usize>>::new(15);
+
+        thread::scope(|s| {
+            for t in 0..THREADS {
+                let queue = &queue;
+                s.spawn(move || {
+                    for ix in 0..PER_THREAD {
+                        let n = t * PER_THREAD + ix;
+                        assert_eq!(Ok(()), queue.push(Prio(n % 97, n)));
+                    }
+                });
+            }
+        });
+
+        let mut prev = 0;
+        while let Some(Prio(prio, _)) = queue.pop() {
+            assert!(prev <= prio);
+            prev = prio;
+        }
+    }
+
+    #[test]
+    fn multi_push_pop() {
+        let queue = MultiQueue::<u16, MaxQueue>::new(2, 0);
+
+        assert_eq!(Ok(()), queue.push(1));
+        assert_eq!(Ok(()), queue.push(2));
+        assert_eq!(Err(()), queue.push(3));
+        assert_eq!(2, queue.len());
+
+        // both roots are compared or remaining queue is swept
+        let mut popped = [queue.pop().unwrap(), queue.pop().unwrap()];
+        popped.sort_unstable();
+
+        assert_eq!([1, 2], popped);
+        assert_eq!(None, queue.pop());
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Queue count must be positive.")]
+    fn multi_no_queue() {
+        _ = MultiQueue::<u16>::new(0, 1);
+    }
+
+    #[test]
+    fn multi_stress() {
+        let queue = MultiQueue::<Prio<usize, usize>>::new(2 * THREADS, 12);
+
+        let seen = thread::scope(|s| {
+            let workers = (0..THREADS)
+                .map(|t| {
+                    let queue = &queue;
+                    s.spawn(move || {
+                        let mut seen = Vec::new();
+                        for ix in 0..PER_THREAD {
+                            let n = t * PER_THREAD + ix;
+                            assert_eq!(Ok(()), queue.push(Prio(n % 97, n)));
+
+                            // `None` is possible only on race with sweep
+                            if ix % 2 == 1 {
+                                if let Some(Prio(_, n)) = queue.pop() {
+                                    seen.push(n);
+                                }
+                            }
+                        }
+                        seen
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            workers
+                .into_iter()
+                .flat_map(|w| w.join().unwrap())
+                .collect::<Vec<usize>>()
+        });
+
+        let mut seen = seen;
+        while let Some(Prio(_, n)) = queue.pop() {
+            seen.push(n);
+        }
+
+        check_seen(seen);
+    }
+}
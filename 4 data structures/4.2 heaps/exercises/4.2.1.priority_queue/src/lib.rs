@@ -1,29 +1,57 @@
-use std::cmp::{Ord, Ordering};
+use std::cmp::{Ord, Ordering, Reverse};
+use std::marker::PhantomData;
+
+use binary_heap::stable::{renumber, tie};
+
+pub mod aging;
+pub mod concurrent;
 
 pub const MAX_LEVELS: usize = 25;
 
-type Form = PriorityQueueForm;
 type Item<T> = PriorityQueueItem<T>;
-type Queue<T> = PriorityQueue<T>;
-
-#[derive(PartialEq, Debug, Clone)]
-#[repr(u8)]
-pub enum PriorityQueueForm {
-    Maximal,
-    Minimal,
-    Undefined,
+type Queue<T, F = MinQueue> = PriorityQueue<T, F>;
+
+/// Type-level queue form
+pub trait QueueForm {
+    /// `true` when `cand` priority ordering
+    /// against `disp` priority puts `cand` closer to root
+    fn precedes(ord: Ordering) -> bool;
+}
+
+/// Least priority is extracted first.
+pub struct MinQueue;
+
+/// Greatest priority is extracted first.
+pub struct MaxQueue;
+
+impl QueueForm for MinQueue {
+    fn precedes(ord: Ordering) -> bool {
+        ord == Ordering::Less
+    }
 }
 
+impl QueueForm for MaxQueue {
+    fn precedes(ord: Ordering) -> bool {
+        ord == Ordering::Greater
+    }
+}
+
+/// Order of items with equal priority
+pub use binary_heap::stable::Ties;
+
 /// Priority queue realized
 /// via fixed binary heap
-pub struct PriorityQueue<T>
+pub struct PriorityQueue<T, F = MinQueue>
 where
     T: Clone + Default + Priority,
+    F: QueueForm,
 {
     data: Box<[Item<T>]>,
     len: usize,
-    form: Form,
+    form: PhantomData<F>,
+    // insertion counter
     rank: u64,
+    ties: Ties,
 }
 
 #[derive(PartialEq, Clone, Debug)]
@@ -32,6 +60,7 @@ where
     T: Priority,
 {
     item: T,
+    // lesser rank precedes
     rank: u64,
 }
 
@@ -40,13 +69,46 @@ pub trait Priority {
     fn prio(&self) -> Self::Priority;
 }
 
-impl Priority for u16 {
-    type Priority = u16;
-    fn prio(&self) -> u16 {
-        let mut num = *self as i32;
-        num = (num - u16::MAX as i32) * -1;
+macro_rules! identity_prio {
+    ($($t:ty),*) => {
+        $(
+            impl Priority for $t {
+                type Priority = $t;
+                fn prio(&self) -> $t {
+                    *self
+                }
+            }
+        )*
+    };
+}
+
+identity_prio!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, char, bool);
 
-        num as u16
+/// Item prioritized by `key`, `value` is carried along
+#[derive(PartialEq, Clone, Default, Debug)]
+pub struct Prio<K, V>(pub K, pub V)
+where
+    K: Ord;
+
+impl<K, V> Priority for Prio<K, V>
+where
+    K: Ord + Clone,
+{
+    type Priority = K;
+    fn prio(&self) -> K {
+        self.0.clone()
+    }
+}
+
+/// Reverses priority of wrapped item, i.e.
+/// `MinQueue` of `Reverse<T>` extracts greatest `T` priority first.
+impl<T> Priority for Reverse<T>
+where
+    T: Priority,
+{
+    type Priority = Reverse<<T as Priority>::Priority>;
+    fn prio(&self) -> Self::Priority {
+        Reverse(self.0.prio())
     }
 }
 
@@ -60,11 +122,17 @@ where
     }
 }
 
-impl<T> Queue<T>
+impl<T, F> Queue<T, F>
 where
     T: Priority + Clone + Default,
+    F: QueueForm,
 {
-    pub fn new(levels: usize, f: Form) -> Self {
+    /// Equal priorities are extracted in insertion order.
+    pub fn new(levels: usize) -> Self {
+        Self::with_ties(levels, Ties::Fifo)
+    }
+
+    pub fn with_ties(levels: usize, ties: Ties) -> Self {
         assert!(
             levels <= MAX_LEVELS,
             "Maximum supported levels is 25. 0 for root only."
@@ -83,8 +151,9 @@ where
         Self {
             data,
             len: 0,
-            form: f,
+            form: PhantomData,
             rank: 0,
+            ties,
         }
     }
 
@@ -98,11 +167,19 @@ where
             return Err(());
         }
 
+        if self.rank == u64::MAX {
+            let live = self.data[..wrix]
+                .iter_mut()
+                .map(|qi| &mut qi.rank)
+                .collect();
+            self.rank = renumber(self.ties, live);
+        }
+
         let rank = self.rank;
-        let que_ite = &mut data[wrix];
+        let que_ite = &mut self.data[wrix];
 
         que_ite.item = t;
-        que_ite.rank = rank;
+        que_ite.rank = tie(self.ties, rank);
 
         self.len = wrix + 1;
         self.rank = rank + 1;
@@ -112,6 +189,14 @@ where
         Ok(())
     }
 
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
     pub fn peek_root(&self) -> Option<&T> {
         if self.len == 0 {
             return None;
@@ -144,8 +229,8 @@ where
     /// `eq_chk` — optional equality check
     /// `bubble_up` is called only from `insert` thus
     /// `cand` has always greater (globally greatest) rank
-    /// therefore is okay to result just on value equality
-    fn for_repl(f: Form, disp: &Item<T>, cand: &Item<T>, eq_chk: bool) -> bool {
+    /// for `Ties::Fifo` therefore is okay to result just on value equality
+    fn for_repl(disp: &Item<T>, cand: &Item<T>, eq_chk: bool) -> bool {
         let mut can_ord = cand.prio().cmp(&disp.prio());
 
         if eq_chk && can_ord == Ordering::Equal {
@@ -157,22 +242,15 @@ where
                 _ => panic!("Code broken elsewhere."),
             }
         } else {
-            if f == Form::Minimal {
-                return can_ord == Ordering::Less;
-            }
-
-            if f == Form::Maximal {
-                return can_ord == Ordering::Greater;
-            }
-
-            panic!("Unsupported queue form.");
+            F::precedes(can_ord)
         }
     }
 
     // `des_ix` = descendant index
     fn bubble_up(&mut self, mut des_ix: usize) {
         let data = &mut self.data;
-        let form = self.form.clone();
+        // `Ties::Lifo` newcomer has globally least rank
+        let eq_chk = self.ties == Ties::Lifo;
 
         while des_ix > 0 {
             // predecessor index
@@ -181,7 +259,7 @@ where
             let predecessor = data[pred_ix].clone();
             let descendant = data[des_ix].clone();
 
-            if Self::for_repl(form.clone(), &predecessor, &descendant, false) {
+            if Self::for_repl(&predecessor, &descendant, eq_chk) {
                 data[pred_ix] = descendant;
                 data[des_ix] = predecessor;
 
@@ -195,7 +273,6 @@ where
     // `pred_ix` = predecessor index
     fn bubble_down(&mut self, mut pred_ix: usize) {
         let len = self.len;
-        let form = self.form.clone();
 
         let data = &mut self.data;
 
@@ -208,13 +285,13 @@ where
             }
 
             let des2_ix = des_ix + 1;
-            if des2_ix < len && Self::for_repl(form.clone(), &data[des_ix], &data[des2_ix], true) {
+            if des2_ix < len && Self::for_repl(&data[des_ix], &data[des2_ix], true) {
                 des_ix = des2_ix;
             }
 
             let predecessor = data[pred_ix].clone();
             let descendant = data[des_ix].clone();
-            if Self::for_repl(form.clone(), &predecessor, &descendant, true) {
+            if Self::for_repl(&predecessor, &descendant, true) {
                 data[pred_ix] = descendant;
                 data[des_ix] = predecessor;
 
@@ -230,11 +307,11 @@ where
 mod tests_of_units {
 
     mod new {
-        use crate::{Form, Queue};
+        use crate::{Queue, Ties};
 
         #[test]
         fn new() {
-            let queue = Queue::<u16>::new(2, Form::Minimal);
+            let queue = Queue::<u16>::new(2);
 
             assert_eq!(0, queue.len);
             assert_eq!(0, queue.rank);
@@ -243,33 +320,25 @@ mod tests_of_units {
                 .iter()
                 .all(|x| x.item == u16::default() && x.rank == 0));
             assert_eq!(2usize.pow(3) - 1, queue.data.len());
-            assert_eq!(Form::Minimal, queue.form);
+            assert_eq!(Ties::Fifo, queue.ties);
         }
 
         #[test]
         #[should_panic(expected = "Maximum supported levels is 25. 0 for root only.")]
         fn unsupported_level_count() {
-            _ = Queue::<u16>::new(26, Form::Minimal);
+            _ = Queue::<u16>::new(26);
         }
     }
 
     mod insertion {
         use super::qi;
-        use crate::{Form, Queue};
+        use crate::{Item, MaxQueue, MinQueue, Queue, QueueForm, Ties};
         use std::ops::Deref;
 
         #[test]
         fn prio() {
-            #[rustfmt::skip]
-            let test_cases = [
-                (Form::Minimal, [8,9], [qi(9,1), qi(8,0), qi(0,0)],),
-                (Form::Maximal, [9,8], [qi(8,1), qi(9,0), qi(0,0)],),
-            ];
-
-            for case in test_cases {
-                let mut queue = Queue::<u16>::new(1, case.0);
-
-                let nums: [u16; 2] = case.1;
+            fn test<F: QueueForm>(nums: [u16; 2], test_data: [Item<u16>; 3]) {
+                let mut queue = Queue::<u16, F>::new(1);
 
                 let mut count = 0;
                 for n in nums {
@@ -278,38 +347,80 @@ mod tests_of_units {
                     assert_eq!(count, queue.len);
                 }
 
-                assert_eq!(&case.2, queue.data.deref());
+                assert_eq!(&test_data, queue.data.deref());
             }
+
+            test::<MaxQueue>([8, 9], [qi(9, 1), qi(8, 0), qi(0, 0)]);
+            test::<MinQueue>([9, 8], [qi(8, 1), qi(9, 0), qi(0, 0)]);
         }
 
         #[test]
         fn rank() {
-            #[rustfmt::skip]
-            let test_cases = [
-                (Form::Minimal, [2,2], [qi(2,0), qi(2,1), qi(0,0)],),
-                (Form::Maximal, [2,2], [qi(2,0), qi(2,1), qi(0,0)],),
-            ];
-
-            for case in test_cases {
-                let mut queue = Queue::<u16>::new(1, case.0);
-
-                let nums: [u16; 2] = case.1;
+            fn test<F: QueueForm>() {
+                let mut queue = Queue::<u16, F>::new(1);
 
                 let mut count = 0;
-                for n in nums {
+                for n in [2, 2] {
                     assert_eq!(Ok(()), queue.insert(n));
                     count += 1;
                     assert_eq!(count, queue.len);
                     assert_eq!(count as u64, queue.rank);
                 }
 
-                assert_eq!(&case.2, queue.data.deref());
+                assert_eq!(&[qi(2, 0), qi(2, 1), qi(0, 0)], queue.data.deref());
+            }
+
+            test::<MinQueue>();
+            test::<MaxQueue>();
+        }
+
+        #[test]
+        fn lifo_rank() {
+            fn test<F: QueueForm>() {
+                let mut queue = Queue::<u16, F>::with_ties(1, Ties::Lifo);
+                assert_eq!(Ties::Lifo, queue.ties);
+
+                for n in [2, 2] {
+                    assert_eq!(Ok(()), queue.insert(n));
+                }
+
+                assert_eq!(2, queue.rank);
+
+                #[rustfmt::skip]
+                let test_data = [qi(2,u64::MAX - 1), qi(2,u64::MAX), qi(0,0)];
+                assert_eq!(&test_data, queue.data.deref());
+            }
+
+            test::<MinQueue>();
+            test::<MaxQueue>();
+        }
+
+        #[test]
+        fn rank_overflow() {
+            let max = u64::MAX;
+            #[rustfmt::skip]
+            let test_cases = [
+                (Ties::Fifo, [qi(5,0), qi(5,1), qi(5,2)],),
+                (Ties::Lifo, [qi(5,max - 2), qi(5,max), qi(5,max - 1)],),
+            ];
+
+            for case in test_cases {
+                let mut queue = Queue::<u16>::with_ties(1, case.0);
+                queue.rank = max - 1;
+
+                for _ in 0..3 {
+                    assert_eq!(Ok(()), queue.insert(5));
+                }
+
+                // 1 insertion before exhaustion, 2 after renumbering
+                assert_eq!(3, queue.rank);
+                assert_eq!(&case.1, queue.data.deref());
             }
         }
 
         #[test]
         fn full_error() {
-            let mut queue = Queue::<u16>::new(0, Form::Minimal);
+            let mut queue = Queue::<u16>::new(0);
 
             assert_eq!(Ok(()), queue.insert(0));
             assert_eq!(Err(()), queue.insert(0));
@@ -317,11 +428,12 @@ mod tests_of_units {
     }
 
     mod peek_root {
-        use crate::{Form, Item, Queue};
+        use crate::{Item, Queue, Ties};
+        use std::marker::PhantomData;
 
         #[test]
         fn none_root() {
-            let queue = Queue::<u16>::new(0, Form::Minimal);
+            let queue = Queue::<u16>::new(0);
             assert_eq!(None, queue.peek_root());
         }
 
@@ -330,8 +442,9 @@ mod tests_of_units {
             let queue: Queue<u16> = Queue {
                 data: Box::new([Item { item: 90, rank: 0 }]),
                 len: 1,
-                form: Form::Minimal,
+                form: PhantomData,
                 rank: 1,
+                ties: Ties::Fifo,
             };
 
             assert_eq!(Some(&queue.data[0].item), queue.peek_root());
@@ -340,22 +453,16 @@ mod tests_of_units {
 
     mod extraction {
         use super::{qi, que_ims};
-        use crate::{Form, Item, Queue};
+        use crate::{Item, MaxQueue, MinQueue, Queue, QueueForm};
 
         #[test]
         fn prio() {
             const LEN: usize = 4;
 
-            let test_cases = [
-                (Form::Maximal, [8, 10, 9, 10], [8, 9, 10, 10]),
-                (Form::Minimal, [10, 8, 9, 8], [10, 9, 8, 8]),
-            ];
-
-            for case in test_cases {
-                let mut queue = Queue::<u16>::new(2, case.0);
+            fn test<F: QueueForm>(input_data: [u16; LEN], output_data: [u16; LEN]) {
+                let mut queue = Queue::<u16, F>::new(2);
                 let data = &mut queue.data;
 
-                let input_data = case.1;
                 for ix in 0..LEN {
                     data[ix] = qi(input_data[ix], ix as u64);
                 }
@@ -363,12 +470,43 @@ mod tests_of_units {
                 queue.len = LEN;
                 let mut queue_len = LEN;
 
-                for num in case.2 {
+                for num in output_data {
                     assert_eq!(Some(num), queue.extract_root());
                     queue_len -= 1;
                     assert_eq!(queue_len, queue.len);
                 }
             }
+
+            test::<MinQueue>([8, 10, 9, 10], [8, 9, 10, 10]);
+            test::<MaxQueue>([10, 8, 9, 8], [10, 9, 8, 8]);
+        }
+
+        #[test]
+        fn reverse() {
+            use std::cmp::Reverse;
+
+            let mut queue = Queue::<Reverse<u16>>::new(2);
+            for n in [8, 10, 9, 10] {
+                assert_eq!(Ok(()), queue.insert(Reverse(n)));
+            }
+
+            for n in [10, 10, 9, 8] {
+                assert_eq!(Some(Reverse(n)), queue.extract_root());
+            }
+        }
+
+        #[test]
+        fn prio_wrapper() {
+            use crate::Prio;
+
+            let mut queue = Queue::<Prio<u8, char>, MaxQueue>::new(2);
+            for (key, value) in [(1, 'b'), (3, 'a'), (1, 'c'), (2, 'd')] {
+                assert_eq!(Ok(()), queue.insert(Prio(key, value)));
+            }
+
+            for value in ['a', 'd', 'b', 'c'] {
+                assert_eq!(Some(value), queue.extract_root().map(|p| p.1));
+            }
         }
 
         #[test]
@@ -377,14 +515,11 @@ mod tests_of_units {
 
             const LEN: usize = 3;
 
-            let test_cases = [(Form::Maximal, [7; LEN]), (Form::Minimal, [8; LEN])];
-
-            for case in test_cases {
-                let mut queue = Queue::<u16>::new(2, case.0);
+            fn test<F: QueueForm>(input_data: [u16; LEN]) {
+                let mut queue = Queue::<u16, F>::new(2);
 
                 {
                     let data = &mut queue.data;
-                    let input_data = case.1;
 
                     for i in 0..LEN {
                         data[i] = qi(input_data[i], i as u64);
@@ -395,7 +530,7 @@ mod tests_of_units {
 
                 let data: *const Item<u16> = queue.data.deref().as_ptr();
 
-                let test_data = que_ims(&case.1);
+                let test_data = que_ims(&input_data);
 
                 let mut queue_len = LEN;
                 for ix in 0..LEN {
@@ -409,91 +544,76 @@ mod tests_of_units {
                     assert_eq!(queue_len, queue.len);
                 }
             }
+
+            test::<MinQueue>([7; LEN]);
+            test::<MaxQueue>([8; LEN]);
         }
 
         #[test]
         fn empty() {
-            let mut queue = Queue::<u16>::new(0, Form::Minimal);
+            let mut queue = Queue::<u16>::new(0);
             assert_eq!(None, queue.extract_root());
         }
     }
 
     mod for_repl {
         use super::qi;
-        use crate::{Form, PriorityQueue};
+        use crate::{Item, MaxQueue, MinQueue, PriorityQueue};
+
+        type MinPq = PriorityQueue<u16, MinQueue>;
+        type MaxPq = PriorityQueue<u16, MaxQueue>;
+        type ForRepl = fn(&Item<u16>, &Item<u16>, bool) -> bool;
 
         #[test]
         fn eq_chk() {
             let test_cases = [
-                (qi(11, 1), qi(11, 2), false, Form::Undefined),
-                (qi(11, 2), qi(11, 1), true, Form::Undefined),
-                (qi(11, 5), qi(10, 5), false, Form::Minimal),
-                (qi(10, 5), qi(11, 5), true, Form::Minimal),
-                (qi(11, 5), qi(10, 5), true, Form::Maximal),
-                (qi(10, 5), qi(11, 5), false, Form::Maximal),
+                (qi(11, 1), qi(11, 2), false, MinPq::for_repl as ForRepl),
+                (qi(11, 2), qi(11, 1), true, MaxPq::for_repl),
+                (qi(11, 5), qi(10, 5), false, MaxPq::for_repl),
+                (qi(10, 5), qi(11, 5), true, MaxPq::for_repl),
+                (qi(11, 5), qi(10, 5), true, MinPq::for_repl),
+                (qi(10, 5), qi(11, 5), false, MinPq::for_repl),
             ];
 
             for case in test_cases {
-                assert_eq!(
-                    case.2,
-                    PriorityQueue::for_repl(case.3, &case.0, &case.1, true)
-                );
+                assert_eq!(case.2, case.3(&case.0, &case.1, true));
             }
         }
 
         #[test]
         fn no_eq_chk() {
             let test_cases = [
-                (qi(11, 5), qi(11, 4), false, Form::Minimal),
-                (qi(11, 5), qi(11, 4), false, Form::Maximal),
-                (qi(10, 4), qi(11, 5), true, Form::Minimal),
-                (qi(11, 5), qi(10, 4), false, Form::Minimal),
-                (qi(11, 4), qi(10, 5), true, Form::Maximal),
-                (qi(10, 5), qi(11, 4), false, Form::Maximal),
+                (qi(11, 5), qi(11, 4), false, MaxPq::for_repl as ForRepl),
+                (qi(11, 5), qi(11, 4), false, MinPq::for_repl),
+                (qi(10, 4), qi(11, 5), true, MaxPq::for_repl),
+                (qi(11, 5), qi(10, 4), false, MaxPq::for_repl),
+                (qi(11, 4), qi(10, 5), true, MinPq::for_repl),
+                (qi(10, 5), qi(11, 4), false, MinPq::for_repl),
             ];
 
             for case in test_cases {
-                assert_eq!(
-                    case.2,
-                    PriorityQueue::for_repl(case.3, &case.0, &case.1, false)
-                );
+                assert_eq!(case.2, case.3(&case.0, &case.1, false));
             }
         }
 
-        #[test]
-        #[should_panic(expected = "Unsupported queue form.")]
-        fn unsupp_form() {
-            _ = PriorityQueue::for_repl(Form::Undefined, &qi(0, 0), &qi(0, 0), false)
-        }
-
         #[test]
         #[should_panic(expected = "Code broken elsewhere.")]
         fn equal_ranks() {
-            _ = PriorityQueue::for_repl(Form::Undefined, &qi(11, 11), &qi(11, 11), true)
+            _ = MinPq::for_repl(&qi(11, 11), &qi(11, 11), true)
         }
     }
 
     mod bubble_up {
         use super::{qi, que_ims};
-        use crate::{Form, Item, Queue};
+        use crate::{Item, MaxQueue, MinQueue, Queue, QueueForm};
         use std::ops::Deref;
 
         #[test]
         fn prio() {
-            #[rustfmt::skip]
-            let test_cases = [
-                (Form::Minimal, [1, 0, 2, 3, 4, 5, 6, 7, 8, 9],
-                [qi(9,9),qi(8,8),qi(5,5),qi(6,6),qi(7,7),qi(1,0),qi(4,4),qi(0,1),qi(3,3),qi(2,2),qi(0,0),qi(0,0),qi(0,0),qi(0,0),qi(0,0)]),
-                (Form::Maximal, [9, 8, 7, 6, 5, 4, 3, 2, 1, 0],
-                [qi(0,9),qi(1,8),qi(4,5),qi(3,6),qi(2,7),qi(8,1),qi(5,4),qi(9,0),qi(6,3),qi(7,2),qi(0,0),qi(0,0),qi(0,0),qi(0,0),qi(0,0)]),
-            ];
-
-            for case in test_cases {
-                let mut queue = Queue::<u16>::new(3, case.0);
+            fn test<F: QueueForm>(nums: [u16; 10], test_data: [Item<u16>; 15]) {
+                let mut queue = Queue::<u16, F>::new(3);
                 let data = queue.data.as_mut_ptr();
 
-                let nums: [u16; 10] = case.1;
-
                 let mut wri_ix = 0;
                 let mut rank = 0;
 
@@ -509,20 +629,23 @@ mod tests_of_units {
                     wri_ix += 1;
                 }
 
-                assert_eq!(&case.2, queue.data.deref());
+                assert_eq!(&test_data, queue.data.deref());
             }
+
+            #[rustfmt::skip]
+            test::<MaxQueue>([1, 0, 2, 3, 4, 5, 6, 7, 8, 9],
+                [qi(9,9),qi(8,8),qi(5,5),qi(6,6),qi(7,7),qi(1,0),qi(4,4),qi(0,1),qi(3,3),qi(2,2),qi(0,0),qi(0,0),qi(0,0),qi(0,0),qi(0,0)]);
+            #[rustfmt::skip]
+            test::<MinQueue>([9, 8, 7, 6, 5, 4, 3, 2, 1, 0],
+                [qi(0,9),qi(1,8),qi(4,5),qi(3,6),qi(2,7),qi(8,1),qi(5,4),qi(9,0),qi(6,3),qi(7,2),qi(0,0),qi(0,0),qi(0,0),qi(0,0),qi(0,0)]);
         }
 
         #[test]
         fn rank() {
-            let test_cases = [(Form::Minimal, [2; 7]), (Form::Maximal, [3; 7])];
-
-            for case in test_cases {
-                let mut queue = Queue::<u16>::new(2, case.0);
+            fn test<F: QueueForm>(nums: [u16; 7]) {
+                let mut queue = Queue::<u16, F>::new(2);
                 let data = queue.data.as_mut_ptr();
 
-                let nums: [u16; 7] = case.1;
-
                 let mut wri_ix = 0;
                 let mut rank = 0;
 
@@ -540,17 +663,21 @@ mod tests_of_units {
 
                 assert_eq!(que_ims(&nums), queue.data.deref());
             }
+
+            test::<MaxQueue>([2; 7]);
+            test::<MinQueue>([3; 7]);
         }
     }
 
     mod bubble_down {
 
         use super::{qi, que_ims};
-        use crate::{Form, Item, Queue};
+        use crate::{Item, MaxQueue, MinQueue, Queue, QueueForm, Ties};
+        use std::marker::PhantomData;
         use std::ops::Deref;
 
         #[test]
-        fn maximal() {
+        fn min_queue() {
             let queue_data: [u16; 15] = [9, 1, 2, 3, 4, 5, 6, 7, 8, 9, 0, 0, 0, 0, 0];
             let mut queue_data = que_ims(&queue_data);
 
@@ -560,11 +687,12 @@ mod tests_of_units {
                 queue_data[i].rank = 0;
             }
 
-            let mut queue: Queue<u16> = Queue {
+            let mut queue: Queue<u16, MinQueue> = Queue {
                 data: Box::new(queue_data),
                 len: 9,
-                form: Form::Maximal,
+                form: PhantomData,
                 rank: 10,
+                ties: Ties::Fifo,
             };
 
             queue.bubble_down(0);
@@ -586,7 +714,7 @@ mod tests_of_units {
         }
 
         #[test]
-        fn minimal() {
+        fn max_queue() {
             let queue_data: [u16; 15] = [0, 8, 7, 6, 5, 4, 3, 2, 1, 0, 0, 0, 0, 0, 0];
             let mut queue_data = que_ims(&queue_data);
 
@@ -596,11 +724,12 @@ mod tests_of_units {
                 queue_data[i].rank = 0;
             }
 
-            let mut queue: Queue<u16> = Queue {
+            let mut queue: Queue<u16, MaxQueue> = Queue {
                 data: Box::new(queue_data),
                 len: 9,
-                form: Form::Minimal,
+                form: PhantomData,
                 rank: 10,
+                ties: Ties::Fifo,
             };
 
             queue.bubble_down(0);
@@ -623,10 +752,8 @@ mod tests_of_units {
 
         #[test]
         fn rank() {
-            let test_cases = [(Form::Minimal, [4; 15]), (Form::Maximal, [4; 15])];
-
-            for case in test_cases {
-                let mut queue_data = que_ims(&case.1);
+            fn test<F: QueueForm>() {
+                let mut queue_data = que_ims(&[4; 15]);
 
                 for i in 10..15 {
                     let qi = &mut queue_data[i];
@@ -636,11 +763,12 @@ mod tests_of_units {
 
                 queue_data[0] = queue_data[9].clone();
 
-                let mut queue: Queue<u16> = Queue {
+                let mut queue: Queue<u16, F> = Queue {
                     data: Box::new(queue_data),
                     len: 9,
-                    form: case.0,
+                    form: PhantomData,
                     rank: 10,
+                    ties: Ties::Fifo,
                 };
 
                 queue.bubble_down(0);
@@ -661,12 +789,15 @@ mod tests_of_units {
                              &[qi(4,8),qi(4,9),
                               qi(4,8),qi(4,9),qi(4,9),qi(4,8),qi(4,6),qi(4,9),qi(4,8),qi(4,9),qi(0,0),qi(0,0),qi(0,0),qi(0,0),qi(0,0)]);
             }
+
+            test::<MinQueue>();
+            test::<MaxQueue>();
         }
 
         // district-start helpers
 
-        fn segment_test(
-            queue: &mut Queue<u16>,
+        fn segment_test<F: QueueForm>(
+            queue: &mut Queue<u16, F>,
             offset: isize,
             bubble_count: isize,
             test_data: &[Item<u16>; 15],
@@ -689,14 +820,27 @@ mod tests_of_units {
     }
 
     mod priority {
+        use crate::{Prio, Priority};
+        use std::cmp::Reverse;
+
+        #[test]
+        fn identity_prio() {
+            assert_eq!(u16::MAX, u16::MAX.prio());
+            assert_eq!(1, 1u16.prio());
+            assert_eq!(-3, (-3i64).prio());
+            assert_eq!('a', 'a'.prio());
+        }
+
+        #[test]
+        fn reverse_prio() {
+            assert_eq!(Reverse(7), Reverse(7u16).prio());
+            assert!(Reverse(1u16).prio() > Reverse(2u16).prio());
+        }
 
-        use crate::Priority;
         #[test]
-        fn u16_prio_test() {
-            assert_eq!(0, u16::MAX.prio());
-            assert_eq!(1, u16::MAX - 1u16.prio());
-            assert_eq!(u16::MAX, 0u16.prio());
-            assert_eq!(u16::MAX - 1, 1u16.prio());
+        fn prio_wrapper() {
+            assert_eq!(3, Prio(3u8, "value").prio());
+            assert_eq!(Reverse(3), Reverse(Prio(3u8, "value")).prio());
         }
     }
 
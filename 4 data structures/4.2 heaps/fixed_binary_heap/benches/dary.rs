@@ -0,0 +1,115 @@
+//! cargo bench --bench dary
+//!
+//! compares arity 2, 4 and 8 on insert-heavy workload
+//! and on full drain
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use fixed_binary_heap::dary::DaryHeap;
+use fixed_binary_heap::FixBinHeapForm;
+
+const LEN: usize = 1 << 16;
+
+// least levels of `D`-ary tree with room for `LEN` nodes
+fn levels<const D: usize>() -> usize {
+    let mut levels = 0;
+    let mut nodes = 1;
+    let mut level = 1;
+
+    while nodes < LEN {
+        level *= D;
+        nodes += level;
+        levels += 1;
+    }
+
+    levels
+}
+
+// xorshift for determinism
+fn pseudo_random(len: usize) -> Vec<u64> {
+    let mut x = 0x9E37_79B9_7F4A_7C15u64;
+    (0..len)
+        .map(|_| {
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            x
+        })
+        .collect()
+}
+
+fn insert<const D: usize>(nums: &[u64]) -> DaryHeap<u64, D> {
+    let mut heap = DaryHeap::<u64, D>::new(levels::<D>(), FixBinHeapForm::Minimal);
+    for n in nums {
+        _ = heap.insert(*n);
+    }
+
+    heap
+}
+
+// 4 insertions per 1 extraction
+fn insert_heavy<const D: usize>(nums: &[u64]) -> u64 {
+    let mut heap = DaryHeap::<u64, D>::new(levels::<D>(), FixBinHeapForm::Minimal);
+
+    let mut sum = 0u64;
+    for (ix, n) in nums.iter().enumerate() {
+        _ = heap.insert(*n);
+
+        if ix % 4 == 3 {
+            sum = sum.wrapping_add(heap.extract_root().unwrap());
+        }
+    }
+
+    sum
+}
+
+fn drain<const D: usize>(mut heap: DaryHeap<u64, D>) -> u64 {
+    let mut sum = 0u64;
+    while let Some(n) = heap.extract_root() {
+        sum = sum.wrapping_add(n);
+    }
+
+    sum
+}
+
+fn arities(c: &mut Criterion) {
+    let nums = pseudo_random(LEN);
+
+    let mut group = c.benchmark_group("insert");
+    group.bench_with_input(BenchmarkId::from_parameter(2), &nums, |b, nums| {
+        b.iter(|| insert::<2>(black_box(nums)))
+    });
+    group.bench_with_input(BenchmarkId::from_parameter(4), &nums, |b, nums| {
+        b.iter(|| insert::<4>(black_box(nums)))
+    });
+    group.bench_with_input(BenchmarkId::from_parameter(8), &nums, |b, nums| {
+        b.iter(|| insert::<8>(black_box(nums)))
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("insert_heavy");
+    group.bench_with_input(BenchmarkId::from_parameter(2), &nums, |b, nums| {
+        b.iter(|| insert_heavy::<2>(black_box(nums)))
+    });
+    group.bench_with_input(BenchmarkId::from_parameter(4), &nums, |b, nums| {
+        b.iter(|| insert_heavy::<4>(black_box(nums)))
+    });
+    group.bench_with_input(BenchmarkId::from_parameter(8), &nums, |b, nums| {
+        b.iter(|| insert_heavy::<8>(black_box(nums)))
+    });
+    group.finish();
+
+    let mut group = c.benchmark_group("drain");
+    group.bench_function(BenchmarkId::from_parameter(2), |b| {
+        b.iter_with_setup(|| insert::<2>(&nums), drain::<2>)
+    });
+    group.bench_function(BenchmarkId::from_parameter(4), |b| {
+        b.iter_with_setup(|| insert::<4>(&nums), drain::<4>)
+    });
+    group.bench_function(BenchmarkId::from_parameter(8), |b| {
+        b.iter_with_setup(|| insert::<8>(&nums), drain::<8>)
+    });
+    group.finish();
+}
+
+criterion_group!(benches, arities);
+criterion_main!(benches);
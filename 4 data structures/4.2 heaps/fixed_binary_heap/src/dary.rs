@@ -0,0 +1,373 @@
+use super::{FixBinHeapCmp, FixBinHeapForm, MAX_LEVELS};
+use std::cmp::Ordering;
+use std::mem::{ManuallyDrop, MaybeUninit};
+
+/// Fixed capacity heap with `D` descendants per node
+/// # Summary
+/// Shallower tree makes `insert` cheaper, Ο(log_D n) comparisons, while `extract_root`
+/// does `D` comparisons per level, Ο(D × log_D n).
+/// <br/>Descendants of one node are adjacent, so they share cache lines.
+/// <br/>`DaryHeap<T, 2>` equals `FixBinHeap<T>`.
+pub struct DaryHeap<T, const D: usize, C = FixBinHeapForm> {
+    data: Box<[MaybeUninit<T>]>,
+    len: usize,
+    form: C,
+}
+
+impl<T, const D: usize, C> DaryHeap<T, D, C>
+where
+    C: FixBinHeapCmp<T>,
+{
+    /// Capacity is `1 + D + D² + … + Dˡᵉᵛᵉˡˢ` nodes, 0 levels for root only.
+    /// <br/>Node count is limited to that of `FixBinHeap` of `MAX_LEVELS`.
+    pub fn new(levels: usize, form: C) -> Self {
+        const { assert!(D >= 2, "Arity must be at least 2.") };
+
+        let count = nodes::<D>(levels);
+        assert!(
+            count.is_some_and(|c| c <= nodes::<2>(MAX_LEVELS).unwrap()),
+            "Node count is greater than maximal heap item count support."
+        );
+
+        let data = Box::new_uninit_slice(count.unwrap());
+        Self { data, len: 0, form }
+    }
+
+    /// Note that heap satiation cannot be guaranteed.
+    /// Heap will support `Vec<T>` capacity nodes.
+    pub fn from_vec(vec: Vec<T>, form: C) -> Self {
+        const { assert!(D >= 2, "Arity must be at least 2.") };
+
+        let mut vec = ManuallyDrop::new(vec);
+
+        let cap = vec.capacity();
+        let len = vec.len();
+        let ptr = vec.as_mut_ptr() as *mut MaybeUninit<T>;
+
+        // `MaybeUninit<T>` is guaranteed to have same layout as `T`
+        // and spare capacity is uninitialized by nature
+        let data = unsafe { Vec::from_raw_parts(ptr, cap, cap) };
+
+        let mut heap = DaryHeap {
+            data: data.into_boxed_slice(),
+            len,
+            form,
+        };
+
+        heap.sort();
+        heap
+    }
+
+    // TC: Ο(n)
+    fn sort(&mut self) {
+        let len = self.len;
+
+        // 1 item is heap already
+        if len < 2 {
+            return;
+        }
+
+        // predecessor of last item is last inner node
+        for ix in (0..=(len - 2) / D).rev() {
+            self.bubble_down(ix);
+        }
+    }
+
+    pub fn insert(&mut self, t: T) -> Result<(), ()> {
+        let wrix = self.len;
+        let data = &mut self.data;
+
+        if wrix == data.len() {
+            return Err(());
+        }
+
+        data[wrix].write(t);
+        self.len = wrix + 1;
+
+        self.bubble_up(wrix);
+
+        Ok(())
+    }
+
+    pub fn peek_root(&self) -> Option<&T> {
+        if self.len == 0 {
+            None
+        } else {
+            Some(unsafe { self.data[0].assume_init_ref() })
+        }
+    }
+
+    pub fn extract_root(&mut self) -> Option<T> {
+        let len = self.len;
+
+        if len == 0 {
+            return None;
+        }
+
+        let new_len = len - 1;
+
+        let data = &mut self.data;
+        let root = unsafe { data[0].assume_init_read() };
+
+        // moved out root slot goes beyond `len`
+        data.swap(0, new_len);
+
+        self.len = new_len;
+        self.bubble_down(0);
+
+        Some(root)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.data.len()
+    }
+
+    // `a` should be closer to root than `b`
+    fn precedes(form: &C, a: &MaybeUninit<T>, b: &MaybeUninit<T>) -> bool {
+        // only slots under `len` are compared
+        let (a, b) = unsafe { (a.assume_init_ref(), b.assume_init_ref()) };
+        form.order(a, b) == Ordering::Greater
+    }
+
+    // `desix` = descendant index
+    fn bubble_up(&mut self, mut desix: usize) {
+        let form = &self.form;
+        let data = &mut self.data;
+
+        while desix > 0 {
+            // predecessor index
+            let predix = (desix - 1) / D;
+
+            if Self::precedes(form, &data[desix], &data[predix]) {
+                data.swap(predix, desix);
+            } else {
+                break;
+            }
+
+            desix = predix;
+        }
+    }
+
+    // `pred_ix` = predecessor index
+    fn bubble_down(&mut self, mut pred_ix: usize) {
+        let len = self.len;
+        let form = &self.form;
+        let data = &mut self.data;
+
+        loop {
+            // first descendant index
+            let first_ix = D * pred_ix + 1;
+
+            if first_ix >= len {
+                break;
+            }
+
+            // most preceding descendant
+            let mut des_ix = first_ix;
+            for ix in first_ix + 1..len.min(first_ix + D) {
+                if Self::precedes(form, &data[ix], &data[des_ix]) {
+                    des_ix = ix;
+                }
+            }
+
+            if !Self::precedes(form, &data[des_ix], &data[pred_ix]) {
+                break;
+            }
+
+            data.swap(pred_ix, des_ix);
+
+            pred_ix = des_ix;
+        }
+    }
+}
+
+impl<T, const D: usize, C> Drop for DaryHeap<T, D, C> {
+    fn drop(&mut self) {
+        for slot in self.data[..self.len].iter_mut() {
+            unsafe { slot.assume_init_drop() }
+        }
+    }
+}
+
+// node count of full tree with `levels` under root, `None` on overflow
+fn nodes<const D: usize>(levels: usize) -> Option<usize> {
+    let mut nodes = 1usize;
+    let mut level = 1usize;
+    for _ in 0..levels {
+        level = level.checked_mul(D)?;
+        nodes = nodes.checked_add(level)?;
+    }
+
+    Some(nodes)
+}
+
+#[cfg(test)]
+mod tests_of_units {
+    use super::super::FixBinHeapForm;
+    use super::{nodes, DaryHeap};
+
+    fn verify<T: PartialOrd, const D: usize>(heap: &DaryHeap<T, D>) {
+        for ix in 1..heap.len {
+            let (des, pred) = unsafe {
+                (
+                    heap.data[ix].assume_init_ref(),
+                    heap.data[(ix - 1) / D].assume_init_ref(),
+                )
+            };
+
+            let ok = match heap.form {
+                FixBinHeapForm::Maximal => pred >= des,
+                FixBinHeapForm::Minimal => pred <= des,
+            };
+
+            assert!(ok, "order broken at {ix}");
+        }
+    }
+
+    fn pseudo_random(len: usize) -> Vec<u32> {
+        let mut x = 0x2545_F491u32;
+        (0..len)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 17;
+                x ^= x << 5;
+                x % 1_000
+            })
+            .collect()
+    }
+
+    fn insert_extract<const D: usize>() {
+        let nums = pseudo_random(300);
+
+        for form in [FixBinHeapForm::Minimal, FixBinHeapForm::Maximal] {
+            let maximal = form == FixBinHeapForm::Maximal;
+
+            // least levels able to hold `nums`
+            let levels = (0..).find(|l| nodes::<D>(*l).unwrap() >= nums.len());
+
+            let mut heap = DaryHeap::<u32, D>::new(levels.unwrap(), form);
+            for n in nums.iter() {
+                assert_eq!(Ok(()), heap.insert(*n));
+            }
+
+            verify(&heap);
+
+            let mut criterion = nums.clone();
+            criterion.sort();
+            if maximal {
+                criterion.reverse();
+            }
+
+            for n in criterion {
+                assert_eq!(Some(n), heap.extract_root());
+            }
+
+            assert_eq!(None, heap.extract_root());
+        }
+    }
+
+    #[test]
+    fn insert_extract_2() {
+        insert_extract::<2>();
+    }
+
+    #[test]
+    fn insert_extract_3() {
+        insert_extract::<3>();
+    }
+
+    #[test]
+    fn insert_extract_4() {
+        insert_extract::<4>();
+    }
+
+    #[test]
+    fn insert_extract_8() {
+        insert_extract::<8>();
+    }
+
+    #[test]
+    fn new() {
+        let heap = DaryHeap::<u8, 4>::new(2, FixBinHeapForm::Minimal);
+
+        assert_eq!(21, heap.capacity());
+        assert!(heap.is_empty());
+        assert_eq!(None, heap.peek_root());
+    }
+
+    #[test]
+    #[should_panic(expected = "Node count is greater than maximal heap item count support.")]
+    fn unsupported_level_count() {
+        _ = DaryHeap::<u8, 8>::new(9, FixBinHeapForm::Minimal);
+    }
+
+    #[test]
+    fn full_error() {
+        let mut heap = DaryHeap::<u8, 3>::new(1, FixBinHeapForm::Maximal);
+        for n in 0..4 {
+            assert_eq!(Ok(()), heap.insert(n));
+        }
+
+        assert_eq!(Err(()), heap.insert(4));
+        assert_eq!(Some(&3), heap.peek_root());
+    }
+
+    #[test]
+    fn from_vec() {
+        let mut vec = Vec::with_capacity(10);
+        vec.extend(pseudo_random(7));
+
+        let ptr = vec.as_ptr();
+        let mut criterion = vec.clone();
+
+        let mut heap = DaryHeap::<u32, 4>::from_vec(vec, FixBinHeapForm::Minimal);
+        assert_eq!(7, heap.len());
+        assert_eq!(10, heap.capacity());
+        assert_eq!(ptr, heap.data.as_ptr() as *const u32);
+        verify(&heap);
+
+        criterion.sort();
+        for n in criterion {
+            assert_eq!(Some(n), heap.extract_root());
+        }
+    }
+
+    #[test]
+    fn from_vec_short() {
+        let heap = DaryHeap::<u8, 8>::from_vec(vec![], FixBinHeapForm::Minimal);
+        assert_eq!(None, heap.peek_root());
+
+        let heap = DaryHeap::<u8, 8>::from_vec(vec![1], FixBinHeapForm::Minimal);
+        assert_eq!(Some(&1), heap.peek_root());
+    }
+
+    #[test]
+    fn comparator() {
+        let mut heap =
+            DaryHeap::<String, 4, _>::new(1, |a: &String, b: &String| a.len().cmp(&b.len()));
+
+        for s in ["ab", "abcd", "a", "abc"] {
+            _ = heap.insert(s.to_string());
+        }
+
+        assert_eq!(Some("abcd".to_string()), heap.extract_root());
+        assert_eq!(Some("abc".to_string()), heap.extract_root());
+    }
+
+    #[test]
+    fn nodes_test() {
+        assert_eq!(Some(1), nodes::<4>(0));
+        assert_eq!(Some(5), nodes::<4>(1));
+        assert_eq!(Some(73), nodes::<8>(2));
+        assert_eq!(Some(15), nodes::<2>(3));
+        assert_eq!(None, nodes::<2>(usize::BITS as usize));
+    }
+}
@@ -0,0 +1,433 @@
+use super::{FixBinHeapCmp, FixBinHeapForm};
+use std::cmp::Ordering;
+
+/// Stable item address returned by `IdxBinHeap::insert`.
+/// <br/>Handle of removed or extracted item is never valid again, even when its
+/// slot is reused by later insertion.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Handle {
+    slot: usize,
+    gen: u32,
+}
+
+struct Node<T> {
+    slot: usize,
+    item: T,
+}
+
+struct Slot {
+    // index of item in heap array, `None` for free slot
+    pos: Option<usize>,
+    // bumped on each release
+    gen: u32,
+}
+
+/// Addressable binary heap
+/// # Summary
+/// Keeps position map `handle ⇒ heap index` up to date on every swap,
+/// thus item can be reprioritized or removed without knowing its index.
+/// <br/>`change_priority`, `remove`, `insert`, `extract_root` TC: Ο(log n)
+/// <br/>`contains`, `get`, `peek_root` TC: Ο(1)
+pub struct IdxBinHeap<T, C = FixBinHeapForm> {
+    data: Vec<Node<T>>,
+    slots: Vec<Slot>,
+    // released slots for reuse
+    free: Vec<usize>,
+    form: C,
+}
+
+impl<T, C> IdxBinHeap<T, C>
+where
+    C: FixBinHeapCmp<T>,
+{
+    pub fn new(form: C) -> Self {
+        Self::with_capacity(0, form)
+    }
+
+    pub fn with_capacity(cap: usize, form: C) -> Self {
+        IdxBinHeap {
+            data: Vec::with_capacity(cap),
+            slots: Vec::with_capacity(cap),
+            free: Vec::new(),
+            form,
+        }
+    }
+
+    pub fn insert(&mut self, t: T) -> Handle {
+        let pos = self.data.len();
+
+        let slot = match self.free.pop() {
+            Some(slot) => {
+                self.slots[slot].pos = Some(pos);
+                slot
+            }
+            None => {
+                self.slots.push(Slot {
+                    pos: Some(pos),
+                    gen: 0,
+                });
+                self.slots.len() - 1
+            }
+        };
+
+        self.data.push(Node { slot, item: t });
+        self.bubble_up(pos);
+
+        Handle {
+            slot,
+            gen: self.slots[slot].gen,
+        }
+    }
+
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.pos(handle).is_some()
+    }
+
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.pos(handle).map(|pos| &self.data[pos].item)
+    }
+
+    /// Replaces item of `handle` by `new` and restores heap order
+    /// whether `new` moves towards root or leaves.
+    /// <br/>Returns replaced item or `Err(())` for invalid `handle`.
+    pub fn change_priority(&mut self, handle: Handle, new: T) -> Result<T, ()> {
+        let pos = self.pos(handle).ok_or(())?;
+
+        let old = std::mem::replace(&mut self.data[pos].item, new);
+        match self.form.order(&self.data[pos].item, &old) {
+            Ordering::Greater => self.bubble_up(pos),
+            Ordering::Less => self.bubble_down(pos),
+            Ordering::Equal => {}
+        }
+
+        Ok(old)
+    }
+
+    /// Returns removed item or `Err(())` for invalid `handle`.
+    pub fn remove(&mut self, handle: Handle) -> Result<T, ()> {
+        let pos = self.pos(handle).ok_or(())?;
+        Ok(self.remove_at(pos))
+    }
+
+    pub fn peek_root(&self) -> Option<&T> {
+        self.data.first().map(|n| &n.item)
+    }
+
+    pub fn root_handle(&self) -> Option<Handle> {
+        self.data.first().map(|n| Handle {
+            slot: n.slot,
+            gen: self.slots[n.slot].gen,
+        })
+    }
+
+    pub fn extract_root(&mut self) -> Option<T> {
+        if self.data.is_empty() {
+            None
+        } else {
+            Some(self.remove_at(0))
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    fn pos(&self, handle: Handle) -> Option<usize> {
+        match self.slots.get(handle.slot) {
+            Some(slot) if slot.gen == handle.gen => slot.pos,
+            _ => None,
+        }
+    }
+
+    fn remove_at(&mut self, pos: usize) -> T {
+        let last_ix = self.data.len() - 1;
+        self.swap(pos, last_ix);
+
+        let node = self.data.pop().unwrap();
+
+        let slot = &mut self.slots[node.slot];
+        slot.pos = None;
+        slot.gen = slot.gen.wrapping_add(1);
+        self.free.push(node.slot);
+
+        // last item moved into `pos` can violate order either way
+        if pos < last_ix {
+            self.bubble_up(pos);
+            self.bubble_down(pos);
+        }
+
+        node.item
+    }
+
+    // `a` should be closer to root than `b`
+    fn precedes(&self, a: usize, b: usize) -> bool {
+        self.form.order(&self.data[a].item, &self.data[b].item) == Ordering::Greater
+    }
+
+    // swaps items and updates their positions
+    fn swap(&mut self, a: usize, b: usize) {
+        self.data.swap(a, b);
+        self.slots[self.data[a].slot].pos = Some(a);
+        self.slots[self.data[b].slot].pos = Some(b);
+    }
+
+    // `desix` = descendant index
+    fn bubble_up(&mut self, mut desix: usize) {
+        while desix > 0 {
+            // predecessor index
+            let predix = (desix - 1) / 2;
+
+            if self.precedes(desix, predix) {
+                self.swap(predix, desix);
+            } else {
+                break;
+            }
+
+            desix = predix;
+        }
+    }
+
+    // `pred_ix` = predecessor index
+    fn bubble_down(&mut self, mut pred_ix: usize) {
+        let len = self.data.len();
+
+        loop {
+            // descendant index
+            let mut des_ix = 2 * pred_ix + 1;
+
+            if des_ix >= len {
+                break;
+            }
+
+            let des2_ix = des_ix + 1;
+            if des2_ix < len && self.precedes(des2_ix, des_ix) {
+                des_ix = des2_ix;
+            }
+
+            if !self.precedes(des_ix, pred_ix) {
+                break;
+            }
+
+            self.swap(pred_ix, des_ix);
+
+            pred_ix = des_ix;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests_of_units {
+    use super::super::FixBinHeapForm;
+    use super::{Handle, IdxBinHeap};
+
+    // checks heap order and position map consistency
+    fn verify<T, C>(heap: &IdxBinHeap<T, C>)
+    where
+        C: super::FixBinHeapCmp<T>,
+    {
+        for ix in 1..heap.data.len() {
+            assert!(!heap.precedes(ix, (ix - 1) / 2), "order broken at {ix}");
+        }
+
+        for (ix, node) in heap.data.iter().enumerate() {
+            assert_eq!(Some(ix), heap.slots[node.slot].pos);
+        }
+
+        let live = heap.slots.iter().filter(|s| s.pos.is_some()).count();
+        assert_eq!(heap.data.len(), live);
+    }
+
+    #[test]
+    fn insert_extract() {
+        let nums = [5, 3, 9, 1, 7, 3, 0];
+
+        for form in [FixBinHeapForm::Minimal, FixBinHeapForm::Maximal] {
+            let maximal = form == FixBinHeapForm::Maximal;
+
+            let mut heap = IdxBinHeap::new(form);
+            for n in nums {
+                heap.insert(n);
+                verify(&heap);
+            }
+
+            let mut criterion = nums.to_vec();
+            criterion.sort();
+            if maximal {
+                criterion.reverse();
+            }
+
+            for n in criterion {
+                assert_eq!(Some(n), heap.extract_root());
+                verify(&heap);
+            }
+
+            assert_eq!(None, heap.extract_root());
+            assert!(heap.is_empty());
+        }
+    }
+
+    #[test]
+    fn handles_follow_items() {
+        let mut heap = IdxBinHeap::new(FixBinHeapForm::Minimal);
+
+        let hs = [40, 10, 30, 20, 50].map(|n| heap.insert(n));
+
+        for (h, n) in hs.iter().zip([40, 10, 30, 20, 50]) {
+            assert_eq!(Some(&n), heap.get(*h));
+        }
+
+        assert_eq!(Some(hs[1]), heap.root_handle());
+    }
+
+    #[test]
+    fn change_priority() {
+        let mut heap = IdxBinHeap::new(FixBinHeapForm::Minimal);
+        let hs = [40, 10, 30, 20, 50].map(|n| heap.insert(n));
+
+        // decrease key
+        assert_eq!(Ok(50), heap.change_priority(hs[4], 5));
+        verify(&heap);
+        assert_eq!(Some(hs[4]), heap.root_handle());
+
+        // increase key
+        assert_eq!(Ok(5), heap.change_priority(hs[4], 60));
+        verify(&heap);
+        assert_eq!(Some(hs[1]), heap.root_handle());
+
+        assert_eq!(Ok(10), heap.change_priority(hs[1], 10));
+        verify(&heap);
+
+        assert_eq!(
+            [10, 20, 30, 40, 60].map(Some),
+            [(); 5].map(|_| heap.extract_root())
+        );
+    }
+
+    #[test]
+    fn remove() {
+        let mut heap = IdxBinHeap::new(FixBinHeapForm::Maximal);
+        let hs = [1, 8, 3, 7, 2, 6, 5].map(|n| heap.insert(n));
+
+        assert_eq!(Ok(7), heap.remove(hs[3]));
+        verify(&heap);
+        assert!(!heap.contains(hs[3]));
+        assert_eq!(None, heap.get(hs[3]));
+        assert_eq!(Err(()), heap.remove(hs[3]));
+        assert_eq!(Err(()), heap.change_priority(hs[3], 0));
+
+        // root
+        assert_eq!(Ok(8), heap.remove(hs[1]));
+        verify(&heap);
+
+        // last
+        let last = heap.data.last().unwrap().slot;
+        let last = hs.iter().find(|h| h.slot == last).copied().unwrap();
+        assert!(heap.remove(last).is_ok());
+        verify(&heap);
+
+        assert_eq!(4, heap.len());
+    }
+
+    #[test]
+    fn stale_handle_of_reused_slot() {
+        let mut heap = IdxBinHeap::new(FixBinHeapForm::Minimal);
+
+        let h = heap.insert(1);
+        assert_eq!(Some(1), heap.extract_root());
+        assert!(!heap.contains(h));
+
+        let h2 = heap.insert(2);
+        assert_eq!(h.slot, h2.slot);
+        assert_ne!(h, h2);
+
+        assert!(!heap.contains(h));
+        assert!(heap.contains(h2));
+        assert_eq!(Err(()), heap.remove(h));
+    }
+
+    #[test]
+    fn foreign_handle() {
+        let heap = IdxBinHeap::<u8>::new(FixBinHeapForm::Minimal);
+        assert!(!heap.contains(Handle { slot: 3, gen: 0 }));
+    }
+
+    #[test]
+    fn pseudo_random_operations() {
+        let mut heap = IdxBinHeap::with_capacity(64, FixBinHeapForm::Minimal);
+        let mut live = Vec::<(Handle, u32)>::new();
+
+        // xorshift for determinism
+        let mut x = 0x9E37_79B9u32;
+        let mut next = || {
+            x ^= x << 13;
+            x ^= x >> 17;
+            x ^= x << 5;
+            x
+        };
+
+        for _ in 0..2_000 {
+            let r = next();
+            match r % 4 {
+                0 | 1 => {
+                    let n = next() % 1_000;
+                    live.push((heap.insert(n), n));
+                }
+                2 if !live.is_empty() => {
+                    let ix = next() as usize % live.len();
+                    let n = next() % 1_000;
+                    assert_eq!(Ok(live[ix].1), heap.change_priority(live[ix].0, n));
+                    live[ix].1 = n;
+                }
+                3 if !live.is_empty() => {
+                    let (h, n) = live.swap_remove(next() as usize % live.len());
+                    assert_eq!(Ok(n), heap.remove(h));
+                }
+                _ => {}
+            }
+
+            verify(&heap);
+            assert_eq!(live.len(), heap.len());
+            assert_eq!(live.iter().map(|l| l.1).min().as_ref(), heap.peek_root());
+        }
+    }
+
+    #[test]
+    fn dijkstra() {
+        // adjacency list of (node, weight)
+        let graph: [&[(usize, u32)]; 5] = [
+            &[(1, 4), (2, 1)],
+            &[(3, 1)],
+            &[(1, 2), (3, 5)],
+            &[(4, 3)],
+            &[],
+        ];
+
+        let mut dist = [u32::MAX; 5];
+        let mut hs = [None; 5];
+        let mut heap = IdxBinHeap::new(|a: &(u32, usize), b: &(u32, usize)| b.cmp(a));
+
+        dist[0] = 0;
+        hs[0] = Some(heap.insert((0, 0)));
+
+        while let Some((d, node)) = heap.extract_root() {
+            for (next, w) in graph[node] {
+                let nd = d + w;
+                if nd < dist[*next] {
+                    dist[*next] = nd;
+                    match hs[*next] {
+                        Some(h) if heap.contains(h) => {
+                            _ = heap.change_priority(h, (nd, *next));
+                        }
+                        _ => hs[*next] = Some(heap.insert((nd, *next))),
+                    }
+                }
+            }
+        }
+
+        assert_eq!([0, 3, 1, 4, 7], dist);
+    }
+}
@@ -0,0 +1,96 @@
+//! HEAPS MERGEABLE WITHOUT REINSERTION
+//!
+//! +---------------+-------------+-----------------+-----------------+----------+
+//! |               | insert      | extract_root    | decrease_key    | meld     |
+//! +---------------+-------------+-----------------+-----------------+----------+
+//! | PairingHeaps  | Ο(1)        | Ο(log n) amort. | o(log n) amort. | Ο(1)     |
+//! | BinomialHeaps | Ο(1) amort. | Ο(log n)        | Ο(log n)        | Ο(log n) |
+//! +---------------+-------------+-----------------+-----------------+----------+
+//!
+//! heaps of one `PairingHeaps` or `BinomialHeaps` share its arena and are addressed
+//! by `HeapId`, so `meld` only links roots and no node is moved
+//!
+//! handles stay valid over melds, slots of extracted items are reused by any heap
+//!
+//! heap melded into other one is consumed, using its `HeapId` again panics
+
+pub mod binomial;
+pub mod pairing;
+
+/// Stable item address returned by `insert`.
+/// <br/>Handle of extracted item is never valid again, even when its
+/// slot is reused by later insertion.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Handle {
+    slot: usize,
+    gen: u32,
+}
+
+/// Heap address returned by `new_heap`.
+/// <br/>Valid only for `PairingHeaps` or `BinomialHeaps` that returned it.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct HeapId(usize);
+
+struct Slot {
+    // arena index of item node, `None` for free slot
+    pos: Option<usize>,
+    // bumped on each release
+    gen: u32,
+}
+
+// handle ⇒ node map
+#[derive(Default)]
+struct Slots {
+    slots: Vec<Slot>,
+    // released slots for reuse
+    free: Vec<usize>,
+}
+
+impl Slots {
+    // slot to be used by next `alloc`
+    fn next_slot(&self) -> usize {
+        self.free.last().copied().unwrap_or(self.slots.len())
+    }
+
+    fn alloc(&mut self, pos: usize) -> Handle {
+        let slot = match self.free.pop() {
+            Some(slot) => slot,
+            None => {
+                self.slots.push(Slot { pos: None, gen: 0 });
+                self.slots.len() - 1
+            }
+        };
+
+        self.slots[slot].pos = Some(pos);
+
+        Handle {
+            slot,
+            gen: self.slots[slot].gen,
+        }
+    }
+
+    fn release(&mut self, slot: usize) {
+        let s = &mut self.slots[slot];
+        s.pos = None;
+        s.gen = s.gen.wrapping_add(1);
+        self.free.push(slot);
+    }
+
+    fn pos(&self, handle: Handle) -> Option<usize> {
+        match self.slots.get(handle.slot) {
+            Some(slot) if slot.gen == handle.gen => slot.pos,
+            _ => None,
+        }
+    }
+
+    fn set(&mut self, slot: usize, pos: usize) {
+        self.slots[slot].pos = Some(pos);
+    }
+
+    fn handle(&self, slot: usize) -> Handle {
+        Handle {
+            slot,
+            gen: self.slots[slot].gen,
+        }
+    }
+}
@@ -0,0 +1,570 @@
+use super::{Handle, HeapId, Slots};
+use crate::{FixBinHeapCmp, FixBinHeapForm};
+use std::cmp::Ordering;
+
+struct Node<T> {
+    item: T,
+    // handle slot of `item`
+    slot: usize,
+    parent: Option<usize>,
+    // descendant of highest degree
+    child: Option<usize>,
+    // next descendant of same parent, of lower degree
+    sibling: Option<usize>,
+    // descendant count
+    degree: usize,
+}
+
+struct Forest {
+    // `roots[k]` is tree of degree k
+    roots: Vec<Option<usize>>,
+    len: usize,
+    melded: bool,
+}
+
+/// Forests of binomial trees, at most one of each degree per heap
+/// # Summary
+/// Tree of degree k holds 2ᵏ nodes, so forest of n items mirrors binary representation of n
+/// and melding is binary addition with carry.
+/// <br/>All heaps share one arena, `decrease_key` swaps items up the tree, handles follow
+/// items through slot map.
+pub struct BinomialHeaps<T, C = FixBinHeapForm> {
+    nodes: Vec<Option<Node<T>>>,
+    // released nodes for reuse
+    free: Vec<usize>,
+    slots: Slots,
+    heaps: Vec<Forest>,
+    form: C,
+}
+
+impl<T, C> BinomialHeaps<T, C>
+where
+    C: FixBinHeapCmp<T>,
+{
+    /// `form` orders items of all heaps.
+    pub fn new(form: C) -> Self {
+        BinomialHeaps {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            slots: Slots::default(),
+            heaps: Vec::new(),
+            form,
+        }
+    }
+
+    pub fn new_heap(&mut self) -> HeapId {
+        self.heaps.push(Forest {
+            roots: Vec::new(),
+            len: 0,
+            melded: false,
+        });
+
+        HeapId(self.heaps.len() - 1)
+    }
+
+    pub fn insert(&mut self, heap: HeapId, t: T) -> Handle {
+        self.forest(heap);
+
+        let ix = self.free.pop().unwrap_or(self.nodes.len());
+        let handle = self.slots.alloc(ix);
+
+        let node = Some(Node {
+            item: t,
+            slot: handle.slot,
+            parent: None,
+            child: None,
+            sibling: None,
+            degree: 0,
+        });
+
+        if ix == self.nodes.len() {
+            self.nodes.push(node);
+        } else {
+            self.nodes[ix] = node;
+        }
+
+        self.add_tree(heap, ix);
+
+        self.heaps[heap.0].len += 1;
+        handle
+    }
+
+    /// TC: Ο(log n)
+    pub fn peek_root(&self, heap: HeapId) -> Option<&T> {
+        self.top(heap).map(|ix| &self.node(ix).item)
+    }
+
+    pub fn root_handle(&self, heap: HeapId) -> Option<Handle> {
+        self.top(heap)
+            .map(|ix| self.slots.handle(self.node(ix).slot))
+    }
+
+    pub fn extract_root(&mut self, heap: HeapId) -> Option<T> {
+        let top = self.top(heap)?;
+
+        let node = self.nodes[top].take().unwrap();
+        self.free.push(top);
+        self.slots.release(node.slot);
+
+        let forest = &mut self.heaps[heap.0];
+        forest.roots[node.degree] = None;
+        forest.len -= 1;
+
+        // descendants are trees of degrees `degree - 1` down to 0
+        let mut curr = node.child;
+        while let Some(c) = curr {
+            let des = self.node_mut(c);
+            curr = des.sibling.take();
+            des.parent = None;
+
+            self.add_tree(heap, c);
+        }
+
+        let roots = &mut self.heaps[heap.0].roots;
+        while let Some(None) = roots.last() {
+            roots.pop();
+        }
+
+        Some(node.item)
+    }
+
+    /// Moves all items of `other` into `heap`, `other` is consumed.
+    /// <br/>Handles of both heaps stay valid.
+    /// <br/>TC: Ο(log n)
+    pub fn meld(&mut self, heap: HeapId, other: HeapId) {
+        assert_ne!(heap, other, "Heap cannot be melded into itself.");
+        self.forest(heap);
+        self.forest(other);
+
+        let consumed = &mut self.heaps[other.0];
+        let roots = std::mem::take(&mut consumed.roots);
+        let len = consumed.len;
+        consumed.len = 0;
+        consumed.melded = true;
+
+        for root in roots.into_iter().flatten() {
+            self.add_tree(heap, root);
+        }
+
+        self.heaps[heap.0].len += len;
+    }
+
+    /// Replaces item of `handle` by `new` that must not move away from root,
+    /// i.e. decreases key for `Minimal` and increases it for `Maximal` form.
+    /// <br/>Returns replaced item or `Err(())` for invalid `handle` or `new`.
+    pub fn decrease_key(&mut self, handle: Handle, new: T) -> Result<T, ()> {
+        let mut ix = self.slots.pos(handle).ok_or(())?;
+
+        if self.form.order(&new, &self.node(ix).item) == Ordering::Less {
+            return Err(());
+        }
+
+        let old = std::mem::replace(&mut self.node_mut(ix).item, new);
+
+        while let Some(parent) = self.node(ix).parent {
+            let (des, pred) = (&self.node(ix).item, &self.node(parent).item);
+            if self.form.order(des, pred) != Ordering::Greater {
+                break;
+            }
+
+            self.swap_items(ix, parent);
+            ix = parent;
+        }
+
+        Ok(old)
+    }
+
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.slots.pos(handle).is_some()
+    }
+
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.slots.pos(handle).map(|ix| &self.node(ix).item)
+    }
+
+    pub fn len(&self, heap: HeapId) -> usize {
+        self.forest(heap).len
+    }
+
+    pub fn is_empty(&self, heap: HeapId) -> bool {
+        self.len(heap) == 0
+    }
+
+    fn forest(&self, heap: HeapId) -> &Forest {
+        let forest = &self.heaps[heap.0];
+        assert!(!forest.melded, "Heap was melded into other one.");
+
+        forest
+    }
+
+    fn node(&self, ix: usize) -> &Node<T> {
+        self.nodes[ix].as_ref().unwrap()
+    }
+
+    fn node_mut(&mut self, ix: usize) -> &mut Node<T> {
+        self.nodes[ix].as_mut().unwrap()
+    }
+
+    // root closest to heap root
+    fn top(&self, heap: HeapId) -> Option<usize> {
+        let mut roots = self.forest(heap).roots.iter().flatten().copied();
+        let first = roots.next()?;
+
+        Some(roots.fold(first, |top, r| {
+            let ord = self.form.order(&self.node(r).item, &self.node(top).item);
+            if ord == Ordering::Greater {
+                r
+            } else {
+                top
+            }
+        }))
+    }
+
+    // adds detached tree with carry propagation
+    fn add_tree(&mut self, heap: HeapId, mut tree: usize) {
+        let mut degree = self.node(tree).degree;
+
+        loop {
+            let roots = &mut self.heaps[heap.0].roots;
+            if roots.len() == degree {
+                roots.push(None);
+            }
+
+            match roots[degree].take() {
+                None => {
+                    roots[degree] = Some(tree);
+                    break;
+                }
+                Some(other) => {
+                    tree = self.link(other, tree);
+                    degree += 1;
+                }
+            }
+        }
+    }
+
+    // links 2 trees of same degree, returns new root
+    fn link(&mut self, a: usize, b: usize) -> usize {
+        let a_item = &self.node(a).item;
+        let b_item = &self.node(b).item;
+
+        let (pred, des) = if self.form.order(b_item, a_item) == Ordering::Greater {
+            (b, a)
+        } else {
+            (a, b)
+        };
+
+        let child = self.node(pred).child;
+
+        let des_node = self.node_mut(des);
+        des_node.parent = Some(pred);
+        des_node.sibling = child;
+
+        let pred_node = self.node_mut(pred);
+        pred_node.child = Some(des);
+        pred_node.degree += 1;
+
+        pred
+    }
+
+    // swaps items of 2 nodes and updates their slots
+    fn swap_items(&mut self, a: usize, b: usize) {
+        let mut a_node = self.nodes[a].take().unwrap();
+        let b_node = self.node_mut(b);
+
+        std::mem::swap(&mut a_node.item, &mut b_node.item);
+        std::mem::swap(&mut a_node.slot, &mut b_node.slot);
+
+        let b_slot = b_node.slot;
+        self.slots.set(a_node.slot, a);
+        self.slots.set(b_slot, b);
+
+        self.nodes[a] = Some(a_node);
+    }
+}
+
+#[cfg(test)]
+mod tests_of_units {
+    use super::super::{Handle, HeapId};
+    use super::BinomialHeaps;
+    use crate::{FixBinHeapCmp, FixBinHeapForm};
+    use std::cmp::Ordering;
+
+    // checks heap order, tree shapes and slot map consistency
+    fn verify<T, C: FixBinHeapCmp<T>>(heaps: &BinomialHeaps<T, C>, heap: HeapId) {
+        // returns node count of subtree
+        fn subtree<T, C: FixBinHeapCmp<T>>(heaps: &BinomialHeaps<T, C>, ix: usize) -> usize {
+            let node = heaps.node(ix);
+            assert_eq!(Some(ix), heaps.slots.slots[node.slot].pos);
+
+            let mut count = 1;
+            let mut degree = node.degree;
+            let mut curr = node.child;
+            while let Some(c) = curr {
+                let des = heaps.node(c);
+                degree -= 1;
+
+                assert_eq!(Some(ix), des.parent);
+                assert_eq!(degree, des.degree);
+                assert_ne!(Ordering::Greater, heaps.form.order(&des.item, &node.item));
+
+                count += subtree(heaps, c);
+                curr = des.sibling;
+            }
+
+            assert_eq!(0, degree);
+            assert_eq!(1 << node.degree, count);
+            count
+        }
+
+        let forest = heaps.forest(heap);
+
+        let mut count = 0;
+        for (degree, root) in forest.roots.iter().enumerate() {
+            if let Some(r) = root {
+                assert_eq!(degree, heaps.node(*r).degree);
+                assert_eq!(None, heaps.node(*r).parent);
+                count += subtree(heaps, *r);
+            }
+        }
+
+        assert_eq!(forest.len, count);
+        // forest mirrors binary representation of length
+        assert_eq!(
+            usize::BITS - forest.len.leading_zeros(),
+            forest.roots.len() as u32
+        );
+    }
+
+    #[test]
+    fn insert_extract() {
+        let nums = [5, 3, 9, 1, 7, 3, 0, 8, 2];
+
+        for form in [FixBinHeapForm::Minimal, FixBinHeapForm::Maximal] {
+            let maximal = form == FixBinHeapForm::Maximal;
+
+            let mut heaps = BinomialHeaps::new(form);
+            let heap = heaps.new_heap();
+            for n in nums {
+                heaps.insert(heap, n);
+                verify(&heaps, heap);
+            }
+
+            assert_eq!(nums.len(), heaps.len(heap));
+
+            let mut criterion = nums.to_vec();
+            criterion.sort();
+            if maximal {
+                criterion.reverse();
+            }
+
+            for n in criterion {
+                assert_eq!(Some(&n), heaps.peek_root(heap));
+                assert_eq!(Some(n), heaps.extract_root(heap));
+                verify(&heaps, heap);
+            }
+
+            assert_eq!(None, heaps.extract_root(heap));
+            assert!(heaps.is_empty(heap));
+        }
+    }
+
+    #[test]
+    fn forest_shape() {
+        let mut heaps = BinomialHeaps::new(FixBinHeapForm::Minimal);
+        let heap = heaps.new_heap();
+        for n in 0..11 {
+            heaps.insert(heap, n);
+        }
+
+        // 11 = 0b1011
+        let degrees = heaps
+            .forest(heap)
+            .roots
+            .iter()
+            .map(|r| r.map(|r| heaps.node(r).degree))
+            .collect::<Vec<Option<usize>>>();
+        assert_eq!(vec![Some(0), Some(1), None, Some(3)], degrees);
+    }
+
+    #[test]
+    fn meld() {
+        let mut heaps = BinomialHeaps::new(FixBinHeapForm::Minimal);
+        let (a, b) = (heaps.new_heap(), heaps.new_heap());
+
+        let ha = [6, 2, 8].map(|n| heaps.insert(a, n));
+        let hb = [5, 1, 7, 3].map(|n| heaps.insert(b, n));
+
+        assert_eq!(Some(1), heaps.extract_root(b));
+
+        heaps.meld(a, b);
+        verify(&heaps, a);
+        assert_eq!(6, heaps.len(a));
+
+        // no handle translation
+        for (h, n) in ha.iter().zip([6, 2, 8]) {
+            assert_eq!(Some(&n), heaps.get(*h));
+        }
+
+        assert!(!heaps.contains(hb[1]));
+        for (h, n) in [(hb[0], 5), (hb[2], 7), (hb[3], 3)] {
+            assert_eq!(Some(&n), heaps.get(h));
+        }
+
+        assert_eq!(Ok(7), heaps.decrease_key(hb[2], 0));
+        verify(&heaps, a);
+        assert_eq!(Some(hb[2]), heaps.root_handle(a));
+
+        let h = heaps.insert(a, 4);
+        assert!(heaps.contains(h));
+        verify(&heaps, a);
+
+        for n in [0, 2, 3, 4, 5, 6, 8] {
+            assert_eq!(Some(n), heaps.extract_root(a));
+        }
+    }
+
+    #[test]
+    fn meld_empty() {
+        let mut heaps = BinomialHeaps::new(FixBinHeapForm::Maximal);
+        let (a, b, c) = (heaps.new_heap(), heaps.new_heap(), heaps.new_heap());
+        let h = heaps.insert(b, 3);
+
+        heaps.meld(a, b);
+        assert_eq!(Some(&3), heaps.get(h));
+        assert_eq!(Some(h), heaps.root_handle(a));
+
+        heaps.meld(a, c);
+        assert_eq!(1, heaps.len(a));
+        verify(&heaps, a);
+    }
+
+    #[test]
+    #[should_panic(expected = "Heap was melded into other one.")]
+    fn melded_id() {
+        let mut heaps = BinomialHeaps::<u8>::new(FixBinHeapForm::Maximal);
+        let (a, b) = (heaps.new_heap(), heaps.new_heap());
+
+        heaps.meld(a, b);
+        heaps.insert(b, 1);
+    }
+
+    #[test]
+    fn decrease_key() {
+        let mut heaps = BinomialHeaps::new(FixBinHeapForm::Maximal);
+        let heap = heaps.new_heap();
+        let hs = [10, 40, 30, 20, 50, 60, 70, 15].map(|n| heaps.insert(heap, n));
+
+        assert_eq!(Ok(10), heaps.decrease_key(hs[0], 80));
+        verify(&heaps, heap);
+        assert_eq!(Some(hs[0]), heaps.root_handle(heap));
+
+        for (h, n) in hs.iter().zip([80, 40, 30, 20, 50, 60, 70, 15]) {
+            assert_eq!(Some(&n), heaps.get(*h));
+        }
+
+        // moving away from root
+        assert_eq!(Err(()), heaps.decrease_key(hs[2], 5));
+        assert_eq!(Some(&30), heaps.get(hs[2]));
+
+        assert_eq!(Some(80), heaps.extract_root(heap));
+        assert_eq!(Err(()), heaps.decrease_key(hs[0], 100));
+
+        assert_eq!(Ok(15), heaps.decrease_key(hs[7], 65));
+        verify(&heaps, heap);
+
+        for n in [70, 65, 60, 50, 40, 30, 20] {
+            assert_eq!(Some(n), heaps.extract_root(heap));
+        }
+    }
+
+    #[test]
+    fn stale_handle() {
+        let mut heaps = BinomialHeaps::new(FixBinHeapForm::Minimal);
+        let heap = heaps.new_heap();
+
+        let h = heaps.insert(heap, 1);
+        _ = heaps.extract_root(heap);
+
+        let h2 = heaps.insert(heap, 2);
+        assert_eq!(h.slot, h2.slot);
+        assert!(!heaps.contains(h));
+        assert!(!heaps.contains(Handle { slot: 9, gen: 0 }));
+    }
+
+    #[test]
+    fn closure_comparator() {
+        let mut heaps = BinomialHeaps::new(|a: &(u8, char), b: &(u8, char)| b.0.cmp(&a.0));
+        let (a, b) = (heaps.new_heap(), heaps.new_heap());
+
+        heaps.insert(a, (3, 'c'));
+        heaps.insert(b, (1, 'a'));
+        let h = heaps.insert(b, (4, 'd'));
+
+        heaps.meld(a, b);
+        assert_eq!(Ok((4, 'd')), heaps.decrease_key(h, (0, 'd')));
+
+        for name in ['d', 'a', 'c'] {
+            assert_eq!(Some(name), heaps.extract_root(a).map(|j| j.1));
+        }
+    }
+
+    #[test]
+    fn pseudo_random_operations() {
+        let mut heaps = BinomialHeaps::new(FixBinHeapForm::Minimal);
+        let mut ids = [heaps.new_heap(), heaps.new_heap()];
+        let mut lives = [0, 1].map(|_| Vec::<(Handle, u32)>::new());
+
+        // xorshift for determinism
+        let mut x = 0x2545_F491u32;
+        let mut next = || {
+            x ^= x << 13;
+            x ^= x >> 17;
+            x ^= x << 5;
+            x
+        };
+
+        for _ in 0..3_000 {
+            let hix = next() as usize % 2;
+            let (heap, live) = (ids[hix], &mut lives[hix]);
+
+            match next() % 8 {
+                0..=3 => {
+                    let n = next() % 1_000;
+                    live.push((heaps.insert(heap, n), n));
+                }
+                4 | 5 if !live.is_empty() => {
+                    let ix = next() as usize % live.len();
+                    let n = live[ix].1 / 2;
+                    assert_eq!(Ok(live[ix].1), heaps.decrease_key(live[ix].0, n));
+                    live[ix].1 = n;
+                }
+                6 => {
+                    let min = live.iter().map(|l| l.1).min();
+                    assert_eq!(min, heaps.extract_root(heap));
+                    if min.is_some() {
+                        // equal items can come out in any order
+                        let ix = live.iter().position(|l| !heaps.contains(l.0));
+                        live.swap_remove(ix.unwrap());
+                    }
+                }
+                7 => {
+                    heaps.meld(ids[0], ids[1]);
+                    ids[1] = heaps.new_heap();
+
+                    let moved = std::mem::take(&mut lives[1]);
+                    lives[0].extend(moved);
+                }
+                _ => {}
+            }
+
+            for (heap, live) in ids.iter().zip(lives.iter()) {
+                verify(&heaps, *heap);
+                assert_eq!(live.len(), heaps.len(*heap));
+                for (h, n) in live {
+                    assert_eq!(Some(n), heaps.get(*h));
+                }
+            }
+        }
+    }
+}
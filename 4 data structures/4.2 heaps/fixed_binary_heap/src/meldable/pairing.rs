@@ -0,0 +1,572 @@
+use super::{Handle, HeapId, Slots};
+use crate::{FixBinHeapCmp, FixBinHeapForm};
+use std::cmp::Ordering;
+
+struct Node<T> {
+    item: T,
+    // heap inserted into, possibly melded into other one since
+    heap: usize,
+    // first descendant
+    child: Option<usize>,
+    // next descendant of same predecessor
+    sibling: Option<usize>,
+    // predecessor for first descendant, left sibling otherwise
+    prev: Option<usize>,
+}
+
+struct Tree {
+    root: Option<usize>,
+    len: usize,
+    // heap melded into, `None` while live
+    into: Option<usize>,
+}
+
+/// Multiway tree heaps linking roots on every insertion and meld
+/// # Summary
+/// Order is restored lazily by two-pass pairing of root descendants on `extract_root`.
+/// <br/>All heaps share one arena, arena index of node equals its handle slot.
+/// <br/>Heap of node is found over meld links, which are shortened on each lookup.
+pub struct PairingHeaps<T, C = FixBinHeapForm> {
+    nodes: Vec<Option<Node<T>>>,
+    slots: Slots,
+    heaps: Vec<Tree>,
+    form: C,
+}
+
+impl<T, C> PairingHeaps<T, C>
+where
+    C: FixBinHeapCmp<T>,
+{
+    /// `form` orders items of all heaps.
+    pub fn new(form: C) -> Self {
+        PairingHeaps {
+            nodes: Vec::new(),
+            slots: Slots::default(),
+            heaps: Vec::new(),
+            form,
+        }
+    }
+
+    pub fn new_heap(&mut self) -> HeapId {
+        self.heaps.push(Tree {
+            root: None,
+            len: 0,
+            into: None,
+        });
+
+        HeapId(self.heaps.len() - 1)
+    }
+
+    pub fn insert(&mut self, heap: HeapId, t: T) -> Handle {
+        let root = self.tree(heap).root;
+
+        // free slot reused or arena grown
+        let slot = self.slots.next_slot();
+        let handle = self.slots.alloc(slot);
+
+        let node = Some(Node {
+            item: t,
+            heap: heap.0,
+            child: None,
+            sibling: None,
+            prev: None,
+        });
+
+        if slot == self.nodes.len() {
+            self.nodes.push(node);
+        } else {
+            self.nodes[slot] = node;
+        }
+
+        let tree = &mut self.heaps[heap.0];
+        tree.len += 1;
+        tree.root = Some(slot);
+
+        if let Some(root) = root {
+            self.heaps[heap.0].root = Some(self.link(root, slot));
+        }
+
+        handle
+    }
+
+    pub fn peek_root(&self, heap: HeapId) -> Option<&T> {
+        self.tree(heap).root.map(|r| &self.node(r).item)
+    }
+
+    pub fn root_handle(&self, heap: HeapId) -> Option<Handle> {
+        self.tree(heap).root.map(|r| self.slots.handle(r))
+    }
+
+    pub fn extract_root(&mut self, heap: HeapId) -> Option<T> {
+        let root = self.tree(heap).root?;
+
+        let node = self.nodes[root].take().unwrap();
+        self.slots.release(root);
+
+        let root = node.child.map(|c| self.combine(c));
+
+        let tree = &mut self.heaps[heap.0];
+        tree.len -= 1;
+        tree.root = root;
+
+        Some(node.item)
+    }
+
+    /// Moves all items of `other` into `heap`, `other` is consumed.
+    /// <br/>Handles of both heaps stay valid.
+    /// <br/>TC: Ο(1)
+    pub fn meld(&mut self, heap: HeapId, other: HeapId) {
+        assert_ne!(heap, other, "Heap cannot be melded into itself.");
+
+        let (a, b) = (self.tree(heap).root, self.tree(other).root);
+
+        let consumed = &mut self.heaps[other.0];
+        let len = consumed.len;
+        consumed.root = None;
+        consumed.len = 0;
+        consumed.into = Some(heap.0);
+
+        let root = match (a, b) {
+            (Some(a), Some(b)) => Some(self.link(a, b)),
+            (a, b) => a.or(b),
+        };
+
+        let tree = &mut self.heaps[heap.0];
+        tree.root = root;
+        tree.len += len;
+    }
+
+    /// Replaces item of `handle` by `new` that must not move away from root,
+    /// i.e. decreases key for `Minimal` and increases it for `Maximal` form.
+    /// <br/>Returns replaced item or `Err(())` for invalid `handle` or `new`.
+    pub fn decrease_key(&mut self, handle: Handle, new: T) -> Result<T, ()> {
+        let ix = self.slots.pos(handle).ok_or(())?;
+
+        if self.form.order(&new, &self.node(ix).item) == Ordering::Less {
+            return Err(());
+        }
+
+        let old = std::mem::replace(&mut self.node_mut(ix).item, new);
+
+        let heap = self.find(self.node(ix).heap);
+        let root = self.heaps[heap].root.unwrap();
+
+        if ix != root {
+            self.cut(ix);
+            self.heaps[heap].root = Some(self.link(root, ix));
+        }
+
+        Ok(old)
+    }
+
+    pub fn contains(&self, handle: Handle) -> bool {
+        self.slots.pos(handle).is_some()
+    }
+
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        self.slots.pos(handle).map(|ix| &self.node(ix).item)
+    }
+
+    pub fn len(&self, heap: HeapId) -> usize {
+        self.tree(heap).len
+    }
+
+    pub fn is_empty(&self, heap: HeapId) -> bool {
+        self.len(heap) == 0
+    }
+
+    fn tree(&self, heap: HeapId) -> &Tree {
+        let tree = &self.heaps[heap.0];
+        assert!(tree.into.is_none(), "Heap was melded into other one.");
+
+        tree
+    }
+
+    // live heap `heap` was melded into, meld links are pointed to it then
+    fn find(&mut self, heap: usize) -> usize {
+        let mut live = heap;
+        while let Some(into) = self.heaps[live].into {
+            live = into;
+        }
+
+        let mut curr = heap;
+        while let Some(into) = self.heaps[curr].into {
+            self.heaps[curr].into = Some(live);
+            curr = into;
+        }
+
+        live
+    }
+
+    fn node(&self, ix: usize) -> &Node<T> {
+        self.nodes[ix].as_ref().unwrap()
+    }
+
+    fn node_mut(&mut self, ix: usize) -> &mut Node<T> {
+        self.nodes[ix].as_mut().unwrap()
+    }
+
+    // links 2 detached trees, returns new root
+    fn link(&mut self, a: usize, b: usize) -> usize {
+        let a_item = &self.node(a).item;
+        let b_item = &self.node(b).item;
+
+        let (pred, des) = if self.form.order(b_item, a_item) == Ordering::Greater {
+            (b, a)
+        } else {
+            (a, b)
+        };
+
+        let child = self.node(pred).child;
+        if let Some(c) = child {
+            self.node_mut(c).prev = Some(des);
+        }
+
+        let des_node = self.node_mut(des);
+        des_node.sibling = child;
+        des_node.prev = Some(pred);
+
+        self.node_mut(pred).child = Some(des);
+
+        pred
+    }
+
+    // detaches subtree of `ix` from its predecessor
+    fn cut(&mut self, ix: usize) {
+        let node = self.node_mut(ix);
+        let prev = node.prev.take().unwrap();
+        let sibling = node.sibling.take();
+
+        let prev_node = self.node_mut(prev);
+        if prev_node.child == Some(ix) {
+            prev_node.child = sibling;
+        } else {
+            prev_node.sibling = sibling;
+        }
+
+        if let Some(s) = sibling {
+            self.node_mut(s).prev = Some(prev);
+        }
+    }
+
+    // two-pass pairing of `first` and its siblings, returns new root
+    fn combine(&mut self, first: usize) -> usize {
+        let mut subs = Vec::new();
+
+        let mut curr = Some(first);
+        while let Some(c) = curr {
+            let node = self.node_mut(c);
+            curr = node.sibling.take();
+            node.prev = None;
+
+            subs.push(c);
+        }
+
+        // left to right pairs
+        let mut paired = Vec::with_capacity(subs.len().div_ceil(2));
+        for pair in subs.chunks(2) {
+            paired.push(match pair {
+                [a, b] => self.link(*a, *b),
+                [a] => *a,
+                _ => unreachable!(),
+            });
+        }
+
+        // right to left accumulation
+        let mut root = paired.pop().unwrap();
+        while let Some(p) = paired.pop() {
+            root = self.link(p, root);
+        }
+
+        root
+    }
+}
+
+#[cfg(test)]
+mod tests_of_units {
+    use super::super::{Handle, HeapId};
+    use super::PairingHeaps;
+    use crate::{FixBinHeapCmp, FixBinHeapForm};
+    use std::cmp::Ordering;
+
+    // checks heap order and links consistency
+    fn verify<T, C: FixBinHeapCmp<T>>(heaps: &PairingHeaps<T, C>, heap: HeapId) {
+        let mut count = 0;
+
+        if let Some(root) = heaps.tree(heap).root {
+            assert_eq!(None, heaps.node(root).prev);
+            assert_eq!(None, heaps.node(root).sibling);
+
+            let mut stack = vec![root];
+            while let Some(ix) = stack.pop() {
+                count += 1;
+
+                let node = heaps.node(ix);
+                let mut prev = ix;
+                let mut curr = node.child;
+                while let Some(c) = curr {
+                    let child = heaps.node(c);
+                    assert_eq!(Some(prev), child.prev);
+                    assert_ne!(Ordering::Greater, heaps.form.order(&child.item, &node.item));
+
+                    stack.push(c);
+                    prev = c;
+                    curr = child.sibling;
+                }
+            }
+        }
+
+        assert_eq!(heaps.len(heap), count);
+    }
+
+    #[test]
+    fn insert_extract() {
+        let nums = [5, 3, 9, 1, 7, 3, 0, 8, 2];
+
+        for form in [FixBinHeapForm::Minimal, FixBinHeapForm::Maximal] {
+            let maximal = form == FixBinHeapForm::Maximal;
+
+            let mut heaps = PairingHeaps::new(form);
+            let heap = heaps.new_heap();
+            for n in nums {
+                heaps.insert(heap, n);
+            }
+
+            verify(&heaps, heap);
+            assert_eq!(nums.len(), heaps.len(heap));
+
+            let mut criterion = nums.to_vec();
+            criterion.sort();
+            if maximal {
+                criterion.reverse();
+            }
+
+            for n in criterion {
+                assert_eq!(Some(&n), heaps.peek_root(heap));
+                assert_eq!(Some(n), heaps.extract_root(heap));
+                verify(&heaps, heap);
+            }
+
+            assert_eq!(None, heaps.extract_root(heap));
+            assert!(heaps.is_empty(heap));
+        }
+    }
+
+    #[test]
+    fn meld() {
+        let mut heaps = PairingHeaps::new(FixBinHeapForm::Minimal);
+        let (a, b) = (heaps.new_heap(), heaps.new_heap());
+
+        let ha = [6, 2, 8].map(|n| heaps.insert(a, n));
+        let hb = [5, 1, 7, 3].map(|n| heaps.insert(b, n));
+
+        assert_eq!(Some(1), heaps.extract_root(b));
+
+        heaps.meld(a, b);
+        verify(&heaps, a);
+        assert_eq!(6, heaps.len(a));
+
+        // no handle translation
+        for (h, n) in ha.iter().zip([6, 2, 8]) {
+            assert_eq!(Some(&n), heaps.get(*h));
+        }
+
+        assert!(!heaps.contains(hb[1]));
+        for (h, n) in [(hb[0], 5), (hb[2], 7), (hb[3], 3)] {
+            assert_eq!(Some(&n), heaps.get(h));
+        }
+
+        assert_eq!(Ok(7), heaps.decrease_key(hb[2], 0));
+        assert_eq!(Some(hb[2]), heaps.root_handle(a));
+
+        // slot freed in `b` reused by `a`
+        let h = heaps.insert(a, 4);
+        assert_eq!(hb[1].slot, h.slot);
+        assert!(heaps.contains(h));
+
+        for n in [0, 2, 3, 4, 5, 6, 8] {
+            assert_eq!(Some(n), heaps.extract_root(a));
+        }
+    }
+
+    #[test]
+    fn meld_empty() {
+        let mut heaps = PairingHeaps::new(FixBinHeapForm::Maximal);
+        let (a, b, c) = (heaps.new_heap(), heaps.new_heap(), heaps.new_heap());
+        let h = heaps.insert(b, 3);
+
+        heaps.meld(a, b);
+        assert_eq!(Some(&3), heaps.get(h));
+        assert_eq!(Some(h), heaps.root_handle(a));
+
+        heaps.meld(a, c);
+        assert_eq!(1, heaps.len(a));
+    }
+
+    #[test]
+    #[should_panic(expected = "Heap was melded into other one.")]
+    fn melded_id() {
+        let mut heaps = PairingHeaps::<u8>::new(FixBinHeapForm::Maximal);
+        let (a, b) = (heaps.new_heap(), heaps.new_heap());
+
+        heaps.meld(a, b);
+        heaps.insert(b, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Heap cannot be melded into itself.")]
+    fn self_meld() {
+        let mut heaps = PairingHeaps::<u8>::new(FixBinHeapForm::Maximal);
+        let a = heaps.new_heap();
+
+        heaps.meld(a, a);
+    }
+
+    #[test]
+    fn decrease_key() {
+        let mut heaps = PairingHeaps::new(FixBinHeapForm::Maximal);
+        let heap = heaps.new_heap();
+        let hs = [10, 40, 30, 20, 50].map(|n| heaps.insert(heap, n));
+
+        // flatten tree
+        assert_eq!(Some(50), heaps.extract_root(heap));
+        verify(&heaps, heap);
+
+        assert_eq!(Ok(10), heaps.decrease_key(hs[0], 60));
+        verify(&heaps, heap);
+        assert_eq!(Some(hs[0]), heaps.root_handle(heap));
+
+        // root itself
+        assert_eq!(Ok(60), heaps.decrease_key(hs[0], 70));
+        verify(&heaps, heap);
+
+        assert_eq!(Ok(20), heaps.decrease_key(hs[3], 35));
+        verify(&heaps, heap);
+
+        // moving away from root
+        assert_eq!(Err(()), heaps.decrease_key(hs[2], 5));
+        assert_eq!(Some(&30), heaps.get(hs[2]));
+
+        // extracted
+        assert_eq!(Err(()), heaps.decrease_key(hs[4], 100));
+
+        for n in [70, 40, 35, 30] {
+            assert_eq!(Some(n), heaps.extract_root(heap));
+        }
+    }
+
+    #[test]
+    fn decrease_key_after_melds() {
+        let mut heaps = PairingHeaps::new(FixBinHeapForm::Minimal);
+        let ids = [0, 1, 2, 3].map(|_| heaps.new_heap());
+        let hs = ids.map(|id| heaps.insert(id, 10 + id.0 as u32));
+
+        // chain of meld links 3 ⇒ 2 ⇒ 1 ⇒ 0
+        heaps.meld(ids[2], ids[3]);
+        heaps.meld(ids[1], ids[2]);
+        heaps.meld(ids[0], ids[1]);
+
+        assert_eq!(Ok(13), heaps.decrease_key(hs[3], 1));
+        assert_eq!(Some(hs[3]), heaps.root_handle(ids[0]));
+        verify(&heaps, ids[0]);
+
+        // links shortened by lookup
+        assert_eq!(Some(0), heaps.heaps[3].into);
+        assert_eq!(Some(0), heaps.heaps[2].into);
+
+        assert_eq!(Ok(12), heaps.decrease_key(hs[2], 0));
+        assert_eq!(Some(0), heaps.extract_root(ids[0]));
+        assert_eq!(3, heaps.len(ids[0]));
+    }
+
+    #[test]
+    fn stale_handle() {
+        let mut heaps = PairingHeaps::new(FixBinHeapForm::Minimal);
+        let heap = heaps.new_heap();
+
+        let h = heaps.insert(heap, 1);
+        _ = heaps.extract_root(heap);
+
+        let h2 = heaps.insert(heap, 2);
+        assert_eq!(h.slot, h2.slot);
+        assert!(!heaps.contains(h));
+        assert_eq!(None, heaps.get(h));
+        assert!(!heaps.contains(Handle { slot: 9, gen: 0 }));
+    }
+
+    #[test]
+    fn closure_comparator() {
+        let mut heaps = PairingHeaps::new(|a: &(u8, char), b: &(u8, char)| b.0.cmp(&a.0));
+        let (a, b) = (heaps.new_heap(), heaps.new_heap());
+
+        heaps.insert(a, (3, 'c'));
+        heaps.insert(b, (1, 'a'));
+        let h = heaps.insert(b, (4, 'd'));
+
+        heaps.meld(a, b);
+        assert_eq!(Ok((4, 'd')), heaps.decrease_key(h, (0, 'd')));
+
+        for name in ['d', 'a', 'c'] {
+            assert_eq!(Some(name), heaps.extract_root(a).map(|j| j.1));
+        }
+    }
+
+    #[test]
+    fn pseudo_random_operations() {
+        let mut heaps = PairingHeaps::new(FixBinHeapForm::Minimal);
+        let mut ids = [heaps.new_heap(), heaps.new_heap()];
+        let mut lives = [0, 1].map(|_| Vec::<(Handle, u32)>::new());
+
+        // xorshift for determinism
+        let mut x = 0x2545_F491u32;
+        let mut next = || {
+            x ^= x << 13;
+            x ^= x >> 17;
+            x ^= x << 5;
+            x
+        };
+
+        for _ in 0..3_000 {
+            let hix = next() as usize % 2;
+            let (heap, live) = (ids[hix], &mut lives[hix]);
+
+            match next() % 8 {
+                0..=3 => {
+                    let n = next() % 1_000;
+                    live.push((heaps.insert(heap, n), n));
+                }
+                4 | 5 if !live.is_empty() => {
+                    let ix = next() as usize % live.len();
+                    let n = live[ix].1 / 2;
+                    assert_eq!(Ok(live[ix].1), heaps.decrease_key(live[ix].0, n));
+                    live[ix].1 = n;
+                }
+                6 => {
+                    let min = live.iter().map(|l| l.1).min();
+                    assert_eq!(min, heaps.extract_root(heap));
+                    if min.is_some() {
+                        // equal items can come out in any order
+                        let ix = live.iter().position(|l| !heaps.contains(l.0));
+                        live.swap_remove(ix.unwrap());
+                    }
+                }
+                7 => {
+                    heaps.meld(ids[0], ids[1]);
+                    ids[1] = heaps.new_heap();
+
+                    let moved = std::mem::take(&mut lives[1]);
+                    lives[0].extend(moved);
+                }
+                _ => {}
+            }
+
+            for (heap, live) in ids.iter().zip(lives.iter()) {
+                verify(&heaps, *heap);
+                assert_eq!(live.len(), heaps.len(*heap));
+                for (h, n) in live {
+                    assert_eq!(Some(n), heaps.get(*h));
+                }
+            }
+        }
+    }
+}
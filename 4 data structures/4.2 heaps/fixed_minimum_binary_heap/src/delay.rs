@@ -0,0 +1,540 @@
+//! Delay queue yielding items once their deadline has passed
+//!
+//! Two timer backends are available.
+//!
+//! |                | heap (`new`) | wheel (`with_wheel`)     |
+//! |----------------|--------------|--------------------------|
+//! | `insert`       | Ο(log n)     | Ο(1)                     |
+//! | `cancel`       | Ο(1)         | Ο(1)                     |
+//! | `poll_expired` | Ο(log n)     | Ο(1) amortized           |
+//! | precision      | exact        | `resolution`             |
+//! | capacity       | fixed        | unbounded                |
+//!
+//! Heap backend extracts items in deadline order, equal deadlines in insertion
+//! order. Wheel backend rounds deadlines up to `resolution` ticks, items due at
+//! same tick are extracted in arbitrary order.
+
+mod wheel;
+
+use super::FixMinBinHeap;
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+use wheel::Wheel;
+
+/// Source of current time
+pub trait Clock {
+    fn now(&self) -> Instant;
+}
+
+/// `Instant::now` clock
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Clock moved only by `advance`, for deterministic scheduling
+pub struct ManualClock {
+    now: Cell<Instant>,
+}
+
+impl ManualClock {
+    pub fn new(now: Instant) -> Self {
+        ManualClock {
+            now: Cell::new(now),
+        }
+    }
+
+    pub fn advance(&self, d: Duration) {
+        self.now.set(self.now.get() + d);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.now.get()
+    }
+}
+
+/// Cancellation handle, stale once item was extracted or cancelled
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, PartialOrd)]
+pub struct Handle {
+    slot: usize,
+    gen: u32,
+}
+
+struct Slot<T> {
+    item: Option<T>,
+    gen: u32,
+}
+
+// derived order: deadline, then insertion
+#[derive(Clone, Default, PartialEq, PartialOrd)]
+struct Timer {
+    offset: Duration,
+    seq: u64,
+    handle: Handle,
+}
+
+enum Timers {
+    Heap {
+        heap: FixMinBinHeap<Timer>,
+        seq: u64,
+        // timers of cancelled items
+        stale: usize,
+    },
+    Wheel {
+        wheel: Wheel,
+        resolution: Duration,
+    },
+}
+
+/// Queue of items with `Instant` deadlines
+/// # Summary
+/// Deadlines are kept as offsets from clock time at construction,
+/// earlier deadlines are treated as due immediately.
+pub struct DelayQueue<T, C = SystemClock>
+where
+    C: Clock,
+{
+    slots: Vec<Slot<T>>,
+    free: Vec<usize>,
+    len: usize,
+    timers: Timers,
+    clock: C,
+    origin: Instant,
+}
+
+impl<T, C> DelayQueue<T, C>
+where
+    C: Clock,
+{
+    /// Heap backend, `levels` as with `FixMinBinHeap`.
+    pub fn new(levels: usize, clock: C) -> Self {
+        let timers = Timers::Heap {
+            heap: FixMinBinHeap::new(levels),
+            seq: 0,
+            stale: 0,
+        };
+
+        Self::with_timers(timers, clock)
+    }
+
+    /// Hierarchical timing wheel backend, deadlines are rounded up
+    /// to `resolution` multiples.
+    pub fn with_wheel(resolution: Duration, clock: C) -> Self {
+        assert!(!resolution.is_zero(), "Resolution must be positive.");
+
+        let timers = Timers::Wheel {
+            wheel: Wheel::new(),
+            resolution,
+        };
+
+        Self::with_timers(timers, clock)
+    }
+
+    fn with_timers(timers: Timers, clock: C) -> Self {
+        let origin = clock.now();
+
+        DelayQueue {
+            slots: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+            timers,
+            clock,
+            origin,
+        }
+    }
+
+    /// Returns `Err(())` when heap backend is full.
+    pub fn insert(&mut self, t: T, deadline: Instant) -> Result<Handle, ()> {
+        let handle = self.next_handle();
+        let offset = deadline.saturating_duration_since(self.origin);
+
+        match &mut self.timers {
+            Timers::Heap { heap, seq, stale } => {
+                if heap.len() == heap.capacity() && *stale > 0 {
+                    compact(heap, &self.slots);
+                    *stale = 0;
+                }
+
+                let timer = Timer {
+                    offset,
+                    seq: *seq,
+                    handle,
+                };
+
+                heap.insert(timer)?;
+                *seq += 1;
+            }
+            Timers::Wheel { wheel, resolution } => {
+                let tick = ticks(offset, *resolution, true);
+                wheel.insert(tick, handle);
+            }
+        }
+
+        if handle.slot == self.slots.len() {
+            self.slots.push(Slot { item: None, gen: 0 });
+        } else {
+            self.free.pop();
+        }
+
+        self.slots[handle.slot].item = Some(t);
+        self.len += 1;
+
+        Ok(handle)
+    }
+
+    /// Deadline is `delay` from clock current time.
+    pub fn insert_after(&mut self, t: T, delay: Duration) -> Result<Handle, ()> {
+        let deadline = self.clock.now() + delay;
+        self.insert(t, deadline)
+    }
+
+    /// Returns `None` when `h` is stale.
+    pub fn cancel(&mut self, h: Handle) -> Option<T> {
+        if !self.contains(h) {
+            return None;
+        }
+
+        if let Timers::Heap { stale, .. } = &mut self.timers {
+            *stale += 1;
+        }
+
+        self.release(h)
+    }
+
+    pub fn contains(&self, h: Handle) -> bool {
+        match self.slots.get(h.slot) {
+            Some(slot) => slot.gen == h.gen && slot.item.is_some(),
+            None => false,
+        }
+    }
+
+    /// Extracts item with deadline passed at `now`, if any.
+    pub fn poll_expired(&mut self, now: Instant) -> Option<T> {
+        let offset = now.saturating_duration_since(self.origin);
+
+        loop {
+            let handle = match &mut self.timers {
+                Timers::Heap { heap, stale, .. } => {
+                    let min = heap.peek_min()?;
+                    let is_live = live(&self.slots, min.handle);
+
+                    if is_live && min.offset > offset {
+                        return None;
+                    }
+
+                    let min = heap.extract_min().unwrap();
+                    if !is_live {
+                        *stale -= 1;
+                        continue;
+                    }
+
+                    min.handle
+                }
+                Timers::Wheel { wheel, resolution } => {
+                    let tick = ticks(offset, *resolution, false);
+                    let slots = &self.slots;
+
+                    wheel.advance(tick, |h| live(slots, h));
+
+                    match wheel.pop_ready() {
+                        Some(h) if live(slots, h) => h,
+                        Some(_) => continue,
+                        None => return None,
+                    }
+                }
+            };
+
+            return self.release(handle);
+        }
+    }
+
+    /// `poll_expired` at clock current time.
+    pub fn poll(&mut self) -> Option<T> {
+        let now = self.clock.now();
+        self.poll_expired(now)
+    }
+
+    pub fn clock(&self) -> &C {
+        &self.clock
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn next_handle(&self) -> Handle {
+        match self.free.last() {
+            Some(&slot) => Handle {
+                slot,
+                gen: self.slots[slot].gen,
+            },
+            None => Handle {
+                slot: self.slots.len(),
+                gen: 0,
+            },
+        }
+    }
+
+    fn release(&mut self, h: Handle) -> Option<T> {
+        let slot = &mut self.slots[h.slot];
+
+        slot.gen = slot.gen.wrapping_add(1);
+        self.free.push(h.slot);
+        self.len -= 1;
+
+        slot.item.take()
+    }
+}
+
+fn live<T>(slots: &[Slot<T>], h: Handle) -> bool {
+    let slot = &slots[h.slot];
+    slot.gen == h.gen && slot.item.is_some()
+}
+
+// removes timers of cancelled items
+fn compact<T>(heap: &mut FixMinBinHeap<Timer>, slots: &[Slot<T>]) {
+    heap.retain(|t| live(slots, t.handle));
+}
+
+fn ticks(offset: Duration, resolution: Duration, round_up: bool) -> u64 {
+    let offset = offset.as_nanos();
+    let resolution = resolution.as_nanos();
+
+    let ticks = if round_up {
+        offset.div_ceil(resolution)
+    } else {
+        offset / resolution
+    };
+
+    u64::try_from(ticks).unwrap_or(u64::MAX)
+}
+
+#[cfg(test)]
+mod tests_of_units {
+    use super::{Clock, DelayQueue, Handle, ManualClock, SystemClock};
+    use std::time::{Duration, Instant};
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    fn queues() -> [DelayQueue<u32, ManualClock>; 2] {
+        let now = Instant::now();
+        [
+            DelayQueue::new(4, ManualClock::new(now)),
+            DelayQueue::with_wheel(ms(1), ManualClock::new(now)),
+        ]
+    }
+
+    fn drain<C: Clock>(queue: &mut DelayQueue<u32, C>, now: Instant) -> Vec<u32> {
+        let mut expired = Vec::new();
+        while let Some(n) = queue.poll_expired(now) {
+            expired.push(n);
+        }
+
+        expired
+    }
+
+    #[test]
+    fn expiration() {
+        for mut queue in queues() {
+            for (n, delay) in [(3, 30), (1, 10), (2, 20), (4, 40)] {
+                assert!(queue.insert_after(n, ms(delay)).is_ok());
+            }
+
+            assert_eq!(4, queue.len());
+            assert_eq!(None, queue.poll());
+
+            queue.clock().advance(ms(9));
+            assert_eq!(None, queue.poll());
+
+            queue.clock().advance(ms(1));
+            assert_eq!(Some(1), queue.poll());
+            assert_eq!(None, queue.poll());
+
+            queue.clock().advance(ms(25));
+
+            let now = queue.clock().now();
+            let mut expired = drain(&mut queue, now);
+            expired.sort();
+
+            assert_eq!(vec![2, 3], expired);
+            assert_eq!(1, queue.len());
+        }
+    }
+
+    #[test]
+    fn past_deadline() {
+        for mut queue in queues() {
+            let past = queue.clock().now() - ms(5);
+            assert!(queue.insert(7, past).is_ok());
+
+            assert_eq!(Some(7), queue.poll());
+            assert!(queue.is_empty());
+        }
+    }
+
+    #[test]
+    fn heap_order() {
+        let now = Instant::now();
+        let mut queue = DelayQueue::new(3, SystemClock);
+
+        for (n, delay) in [(4, 30), (1, 10), (2, 10), (3, 20), (5, 30)] {
+            _ = queue.insert(n, now + ms(delay));
+        }
+
+        assert_eq!(vec![1, 2, 3, 4, 5], drain(&mut queue, now + ms(30)));
+    }
+
+    #[test]
+    fn cancellation() {
+        for mut queue in queues() {
+            let a = queue.insert_after(1, ms(10)).unwrap();
+            let b = queue.insert_after(2, ms(10)).unwrap();
+
+            assert!(queue.contains(a));
+            assert_eq!(Some(1), queue.cancel(a));
+            assert!(!queue.contains(a));
+            assert_eq!(None, queue.cancel(a));
+            assert_eq!(1, queue.len());
+
+            // slot is reused, stale handle does not cancel new item
+            let c = queue.insert_after(3, ms(10)).unwrap();
+            assert_eq!(None, queue.cancel(a));
+            assert_ne!(a, c);
+
+            queue.clock().advance(ms(10));
+            let now = queue.clock().now();
+            let mut expired = drain(&mut queue, now);
+            expired.sort();
+
+            assert_eq!(vec![2, 3], expired);
+            assert_eq!(None, queue.cancel(b));
+            assert_eq!(None, queue.cancel(Handle { slot: 9, gen: 0 }));
+        }
+    }
+
+    #[test]
+    fn heap_full() {
+        let now = Instant::now();
+        let mut queue = DelayQueue::new(1, ManualClock::new(now));
+
+        let handles = [1, 2, 3].map(|n| queue.insert(n, now + ms(n as u64)).unwrap());
+        assert_eq!(Err(()), queue.insert(4, now));
+        assert_eq!(3, queue.len());
+
+        // cancelled timers are compacted on full heap
+        assert_eq!(Some(1), queue.cancel(handles[0]));
+        assert_eq!(Some(3), queue.cancel(handles[2]));
+        assert!(queue.insert(5, now + ms(5)).is_ok());
+        assert!(queue.insert(4, now + ms(4)).is_ok());
+        assert_eq!(Err(()), queue.insert(6, now));
+
+        assert_eq!(vec![2, 4, 5], drain(&mut queue, now + ms(5)));
+    }
+
+    #[test]
+    fn wheel_long_delays() {
+        let now = Instant::now();
+        let mut queue = DelayQueue::with_wheel(ms(1), ManualClock::new(now));
+
+        let delays = [ms(70), ms(5_000), ms(300_000), ms(86_400_000)];
+        for (n, delay) in delays.iter().enumerate() {
+            _ = queue.insert(n as u32, now + *delay);
+        }
+
+        for (n, delay) in delays.iter().enumerate() {
+            assert_eq!(None, queue.poll_expired(now + *delay - ms(1)));
+            assert_eq!(Some(n as u32), queue.poll_expired(now + *delay));
+        }
+
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn wheel_overflow() {
+        let now = Instant::now();
+        // 2^36 ns is wheel range
+        let mut queue = DelayQueue::with_wheel(Duration::from_nanos(1), ManualClock::new(now));
+
+        let far = Duration::from_secs(200);
+        _ = queue.insert(1, now + far);
+        _ = queue.insert(2, now + ms(1));
+
+        assert_eq!(Some(2), queue.poll_expired(now + ms(1)));
+        assert_eq!(
+            None,
+            queue.poll_expired(now + far - Duration::from_nanos(1))
+        );
+        assert_eq!(Some(1), queue.poll_expired(now + far));
+    }
+
+    #[test]
+    fn wheel_rounds_up() {
+        let now = Instant::now();
+        let mut queue = DelayQueue::with_wheel(ms(10), ManualClock::new(now));
+
+        _ = queue.insert(1, now + ms(11));
+
+        assert_eq!(None, queue.poll_expired(now + ms(19)));
+        assert_eq!(Some(1), queue.poll_expired(now + ms(20)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Resolution must be positive.")]
+    fn zero_resolution() {
+        _ = DelayQueue::<u32, _>::with_wheel(Duration::ZERO, SystemClock);
+    }
+
+    #[test]
+    fn backends_agree() {
+        let now = Instant::now();
+        let mut heap = DelayQueue::new(10, ManualClock::new(now));
+        let mut wheel = DelayQueue::with_wheel(ms(1), ManualClock::new(now));
+
+        let mut seed = 0x2545_f491_u64;
+        let mut next = || {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed
+        };
+
+        let mut handles = Vec::new();
+        for n in 0..1_000 {
+            let deadline = now + ms(next() % 100_000);
+            let h = heap.insert(n, deadline).unwrap();
+            let w = wheel.insert(n, deadline).unwrap();
+            handles.push((h, w));
+
+            if next() % 4 == 0 {
+                let (h, w) = handles[next() as usize % handles.len()];
+                assert_eq!(heap.cancel(h), wheel.cancel(w));
+            }
+        }
+
+        let mut at = now;
+        while !heap.is_empty() {
+            at += ms(next() % 5_000);
+
+            let mut heap_expired = drain(&mut heap, at);
+            let mut wheel_expired = drain(&mut wheel, at);
+
+            heap_expired.sort();
+            wheel_expired.sort();
+
+            assert_eq!(heap_expired, wheel_expired);
+        }
+
+        assert!(wheel.is_empty());
+    }
+}
@@ -0,0 +1,195 @@
+use super::Handle;
+use std::collections::VecDeque;
+use std::mem;
+
+const SLOT_BITS: u32 = 6;
+const SLOTS: usize = 1 << SLOT_BITS;
+const LEVELS: usize = 6;
+// ticks covered by all levels
+const RANGE_BITS: u32 = SLOT_BITS * LEVELS as u32;
+
+#[derive(Clone, Copy)]
+struct Entry {
+    tick: u64,
+    handle: Handle,
+}
+
+/// Hierarchical timing wheel
+/// # Summary
+/// Level `l` slot spans `64^l` ticks. Entry is placed on level of highest
+/// 6-bit digit in which its tick differs from `elapsed`, into slot given
+/// by that digit of its tick. When `elapsed` reaches slot start, slot entries
+/// are placed again, either to lower levels or to ready list.
+/// <br/>Entries beyond `2^36` ticks wait in overflow list.
+pub(super) struct Wheel {
+    slots: Box<[Vec<Entry>]>,
+    elapsed: u64,
+    ready: VecDeque<Handle>,
+    overflow: Vec<Entry>,
+}
+
+impl Wheel {
+    pub(super) fn new() -> Self {
+        Wheel {
+            slots: vec![Vec::new(); SLOTS * LEVELS].into_boxed_slice(),
+            elapsed: 0,
+            ready: VecDeque::new(),
+            overflow: Vec::new(),
+        }
+    }
+
+    pub(super) fn insert(&mut self, tick: u64, handle: Handle) {
+        self.place(Entry { tick, handle });
+    }
+
+    fn place(&mut self, e: Entry) {
+        if e.tick <= self.elapsed {
+            self.ready.push_back(e.handle);
+            return;
+        }
+
+        let diff = e.tick ^ self.elapsed;
+        let level = ((u64::BITS - 1 - diff.leading_zeros()) / SLOT_BITS) as usize;
+
+        if level >= LEVELS {
+            self.overflow.push(e);
+            return;
+        }
+
+        let slot = (e.tick >> (level as u32 * SLOT_BITS)) as usize & (SLOTS - 1);
+        self.slots[level * SLOTS + slot].push(e);
+    }
+
+    /// Moves entries due at `now` to ready list, entries not `live` are dropped.
+    pub(super) fn advance(&mut self, now: u64, live: impl Fn(Handle) -> bool) {
+        while self.elapsed < now {
+            let (elapsed, slot) = match self.next_slot() {
+                Some((start, ix)) if start <= now => (start, Some(ix)),
+                _ => (now, None),
+            };
+
+            let epoch_changed = (self.elapsed ^ elapsed) >> RANGE_BITS != 0;
+            self.elapsed = elapsed;
+
+            if epoch_changed {
+                for e in mem::take(&mut self.overflow) {
+                    self.place(e);
+                }
+            }
+
+            if let Some(ix) = slot {
+                for e in mem::take(&mut self.slots[ix]) {
+                    if live(e.handle) {
+                        self.place(e);
+                    }
+                }
+            }
+        }
+    }
+
+    pub(super) fn pop_ready(&mut self) -> Option<Handle> {
+        self.ready.pop_front()
+    }
+
+    // start tick and index of earliest occupied slot
+    //
+    // entries on level `l` are beyond current level `l` slot while entries
+    // on lower levels are within it thus lowest occupied level is earliest
+    fn next_slot(&self) -> Option<(u64, usize)> {
+        for level in 0..LEVELS {
+            let shift = level as u32 * SLOT_BITS;
+            let digit = (self.elapsed >> shift) as usize & (SLOTS - 1);
+
+            for slot in digit + 1..SLOTS {
+                let ix = level * SLOTS + slot;
+
+                if !self.slots[ix].is_empty() {
+                    let upper = self.elapsed >> (shift + SLOT_BITS) << (shift + SLOT_BITS);
+                    return Some((upper | (slot as u64) << shift, ix));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests_of_units {
+    use super::{Handle, Wheel, RANGE_BITS};
+
+    fn h(slot: usize) -> Handle {
+        Handle { slot, gen: 0 }
+    }
+
+    fn ready(wheel: &mut Wheel) -> Vec<usize> {
+        let mut slots = Vec::new();
+        while let Some(h) = wheel.pop_ready() {
+            slots.push(h.slot);
+        }
+
+        slots
+    }
+
+    #[test]
+    fn place() {
+        let mut wheel = Wheel::new();
+        wheel.elapsed = 100;
+
+        #[rustfmt::skip]
+        let test_cases = [
+            (100, None),
+            (101, Some(101 % 64)),
+            (128, Some(64 + 2)),
+            (64 * 64, Some(2 * 64 + 1)),
+            (1 << RANGE_BITS, None),
+        ];
+
+        for (ix, (tick, ix_criterion)) in test_cases.into_iter().enumerate() {
+            wheel.insert(tick, h(ix));
+
+            if let Some(slot_ix) = ix_criterion {
+                assert_eq!(ix, wheel.slots[slot_ix].last().unwrap().handle.slot);
+            }
+        }
+
+        assert_eq!(vec![0], ready(&mut wheel));
+        assert_eq!(1, wheel.overflow.len());
+    }
+
+    #[test]
+    fn next_slot() {
+        let mut wheel = Wheel::new();
+        assert_eq!(None, wheel.next_slot());
+
+        wheel.elapsed = 70;
+        wheel.insert(5000, h(0));
+        assert_eq!(Some((4096, 2 * 64 + 1)), wheel.next_slot());
+
+        wheel.insert(200, h(1));
+        assert_eq!(Some((192, 64 + 3)), wheel.next_slot());
+
+        wheel.insert(71, h(2));
+        assert_eq!(Some((71, 7)), wheel.next_slot());
+    }
+
+    #[test]
+    fn advance() {
+        let mut wheel = Wheel::new();
+
+        for tick in [3, 64, 65, 4100, 5000] {
+            wheel.insert(tick, h(tick as usize));
+        }
+
+        wheel.advance(64, |_| true);
+        assert_eq!(vec![3, 64], ready(&mut wheel));
+
+        wheel.advance(4100, |h| h.slot != 4100);
+        assert_eq!(vec![65], ready(&mut wheel));
+        assert_eq!(4100, wheel.elapsed);
+
+        wheel.advance(10_000, |_| true);
+        assert_eq!(vec![5000], ready(&mut wheel));
+        assert_eq!(None, wheel.next_slot());
+    }
+}
@@ -0,0 +1,520 @@
+use super::MAX_LEVELS;
+use std::mem::MaybeUninit;
+
+/// Double-ended fixed capacity heap
+/// # Summary
+/// Levels alternate, even levels (root included) are minimal, odd ones maximal.
+/// Item on minimal level is least of its subtree, on maximal level greatest.
+/// <br/>Minimum is root, maximum is one of root descendants.
+/// <br/>`insert`, `extract_min`, `extract_max` TC: Ο(log n)
+/// <br/>`peek_min`, `peek_max` TC: Ο(1)
+pub struct FixMinMaxHeap<T>
+where
+    T: PartialOrd,
+{
+    data: Box<[MaybeUninit<T>]>,
+    len: usize,
+}
+
+impl<T> FixMinMaxHeap<T>
+where
+    T: PartialOrd,
+{
+    pub fn new(levels: usize) -> Self {
+        assert!(
+            levels <= MAX_LEVELS,
+            "Maximum supported levels is 25. 0 for root only."
+        );
+
+        let nodes = 2usize.pow((levels + 1) as u32) - 1;
+        Self::with_capacity(nodes)
+    }
+
+    // capacity not aligned to levels
+    fn with_capacity(cap: usize) -> Self {
+        FixMinMaxHeap {
+            data: Box::new_uninit_slice(cap),
+            len: 0,
+        }
+    }
+
+    pub fn insert(&mut self, t: T) -> Result<(), ()> {
+        let wrix = self.len;
+
+        if wrix == self.data.len() {
+            return Err(());
+        }
+
+        self.data[wrix].write(t);
+        self.len = wrix + 1;
+
+        self.bubble_up(wrix);
+
+        Ok(())
+    }
+
+    pub fn peek_min(&self) -> Option<&T> {
+        if self.len == 0 {
+            None
+        } else {
+            Some(self.item(0))
+        }
+    }
+
+    pub fn peek_max(&self) -> Option<&T> {
+        self.max_ix().map(|ix| self.item(ix))
+    }
+
+    pub fn extract_min(&mut self) -> Option<T> {
+        if self.len == 0 {
+            None
+        } else {
+            Some(self.extract(0))
+        }
+    }
+
+    pub fn extract_max(&mut self) -> Option<T> {
+        self.max_ix().map(|ix| self.extract(ix))
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.len == self.data.len()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.data.len()
+    }
+
+    fn item(&self, ix: usize) -> &T {
+        // only slots under `len` are accessed
+        unsafe { self.data[ix].assume_init_ref() }
+    }
+
+    // `a` should be closer to root than `b` on level of `a`
+    fn precedes(&self, a: usize, b: usize, min: bool) -> bool {
+        if min {
+            self.item(a) < self.item(b)
+        } else {
+            self.item(a) > self.item(b)
+        }
+    }
+
+    fn max_ix(&self) -> Option<usize> {
+        match self.len {
+            0 => None,
+            1 => Some(0),
+            2 => Some(1),
+            _ => Some(if self.item(2) > self.item(1) { 2 } else { 1 }),
+        }
+    }
+
+    fn extract(&mut self, ix: usize) -> T {
+        let new_len = self.len - 1;
+
+        let data = &mut self.data;
+        let item = unsafe { data[ix].assume_init_read() };
+
+        // moved out slot goes beyond `len`
+        data.swap(ix, new_len);
+        self.len = new_len;
+
+        if ix < new_len {
+            self.trickle_down(ix);
+        }
+
+        item
+    }
+
+    // `desix` = descendant index
+    fn bubble_up(&mut self, desix: usize) {
+        if desix == 0 {
+            return;
+        }
+
+        let predix = (desix - 1) / 2;
+        let min = is_min_level(desix);
+
+        // item belongs to opposite levels
+        if self.precedes(predix, desix, min) {
+            self.data.swap(predix, desix);
+            self.bubble_up_grand(predix, !min);
+        } else {
+            self.bubble_up_grand(desix, min);
+        }
+    }
+
+    // bubbles through levels of same kind
+    fn bubble_up_grand(&mut self, mut desix: usize, min: bool) {
+        // grandpredecessor exists from level 2
+        while desix > 2 {
+            let grandix = ((desix - 1) / 2 - 1) / 2;
+
+            if self.precedes(desix, grandix, min) {
+                self.data.swap(grandix, desix);
+            } else {
+                break;
+            }
+
+            desix = grandix;
+        }
+    }
+
+    // `pred_ix` = predecessor index
+    fn trickle_down(&mut self, mut pred_ix: usize) {
+        let len = self.len;
+        let min = is_min_level(pred_ix);
+
+        loop {
+            let first_ix = 2 * pred_ix + 1;
+            if first_ix >= len {
+                break;
+            }
+
+            // most preceding of descendants and granddescendants
+            let mut top_ix = first_ix;
+            let grand_ix = 2 * first_ix + 1;
+            for ix in std::iter::once(first_ix + 1).chain(grand_ix..grand_ix + 4) {
+                if ix < len && self.precedes(ix, top_ix, min) {
+                    top_ix = ix;
+                }
+            }
+
+            if !self.precedes(top_ix, pred_ix, min) {
+                break;
+            }
+
+            self.data.swap(top_ix, pred_ix);
+
+            // descendant is on opposite level, no granddescendants below
+            if top_ix <= first_ix + 1 {
+                break;
+            }
+
+            // item moved down may violate its new predecessor
+            let mid_ix = (top_ix - 1) / 2;
+            if self.precedes(mid_ix, top_ix, min) {
+                self.data.swap(mid_ix, top_ix);
+            }
+
+            pred_ix = top_ix;
+        }
+    }
+}
+
+impl<T> Drop for FixMinMaxHeap<T>
+where
+    T: PartialOrd,
+{
+    fn drop(&mut self) {
+        for slot in self.data[..self.len].iter_mut() {
+            unsafe { slot.assume_init_drop() }
+        }
+    }
+}
+
+// even levels are minimal
+fn is_min_level(ix: usize) -> bool {
+    let level = usize::BITS - 1 - (ix + 1).leading_zeros();
+    level.is_multiple_of(2)
+}
+
+/// Bounded collection keeping `k` greatest items
+/// # Summary
+/// When full, least item is evicted on insertion of greater one.
+/// <br/>Wrap items into `std::cmp::Reverse` to keep least ones.
+/// <br/>`offer` TC: Ο(log k)
+pub struct KeepBest<T>
+where
+    T: PartialOrd,
+{
+    heap: FixMinMaxHeap<T>,
+}
+
+impl<T> KeepBest<T>
+where
+    T: PartialOrd,
+{
+    pub fn new(k: usize) -> Self {
+        assert!(
+            k < 2usize.pow((MAX_LEVELS + 1) as u32),
+            "Maximal item count supported is that of 25 levels heap."
+        );
+
+        KeepBest {
+            heap: FixMinMaxHeap::with_capacity(k),
+        }
+    }
+
+    /// Returns item that is not kept, either evicted worst one or `t` itself.
+    pub fn offer(&mut self, t: T) -> Option<T> {
+        if !self.heap.is_full() {
+            _ = self.heap.insert(t);
+            return None;
+        }
+
+        match self.heap.peek_min() {
+            Some(worst) if *worst < t => {
+                let worst = self.heap.extract_min();
+                _ = self.heap.insert(t);
+                worst
+            }
+            _ => Some(t),
+        }
+    }
+
+    pub fn peek_best(&self) -> Option<&T> {
+        self.heap.peek_max()
+    }
+
+    pub fn peek_worst(&self) -> Option<&T> {
+        self.heap.peek_min()
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Best item first.
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut vec = Vec::with_capacity(self.heap.len());
+        while let Some(t) = self.heap.extract_max() {
+            vec.push(t);
+        }
+
+        vec
+    }
+}
+
+#[cfg(test)]
+mod tests_of_units {
+    use super::{is_min_level, FixMinMaxHeap, KeepBest};
+
+    fn verify<T: PartialOrd + std::fmt::Debug>(heap: &FixMinMaxHeap<T>) {
+        for ix in 1..heap.len {
+            let mut pred_ix = ix;
+            while pred_ix > 0 {
+                pred_ix = (pred_ix - 1) / 2;
+
+                let (pred, des) = (heap.item(pred_ix), heap.item(ix));
+                if is_min_level(pred_ix) {
+                    assert!(pred <= des, "{:?} > {:?} at {ix}", pred, des);
+                } else {
+                    assert!(pred >= des, "{:?} < {:?} at {ix}", pred, des);
+                }
+            }
+        }
+    }
+
+    // xorshift for determinism
+    fn pseudo_random(len: usize) -> Vec<u32> {
+        let mut x = 0x9E37_79B9u32;
+        (0..len)
+            .map(|_| {
+                x ^= x << 13;
+                x ^= x >> 17;
+                x ^= x << 5;
+                x % 500
+            })
+            .collect()
+    }
+
+    #[test]
+    fn new() {
+        let heap = FixMinMaxHeap::<u8>::new(2);
+        assert_eq!(7, heap.capacity());
+        assert!(heap.is_empty());
+        assert_eq!(None, heap.peek_min());
+        assert_eq!(None, heap.peek_max());
+    }
+
+    #[test]
+    #[should_panic(expected = "Maximum supported levels is 25. 0 for root only.")]
+    fn unsupported_level_count() {
+        _ = FixMinMaxHeap::<u8>::new(26);
+    }
+
+    #[test]
+    fn full_error() {
+        let mut heap = FixMinMaxHeap::new(0);
+        assert_eq!(Ok(()), heap.insert(1));
+        assert!(heap.is_full());
+        assert_eq!(Err(()), heap.insert(2));
+    }
+
+    #[test]
+    fn small() {
+        let mut heap = FixMinMaxHeap::new(1);
+
+        _ = heap.insert(5);
+        assert_eq!((Some(&5), Some(&5)), (heap.peek_min(), heap.peek_max()));
+
+        _ = heap.insert(3);
+        assert_eq!((Some(&3), Some(&5)), (heap.peek_min(), heap.peek_max()));
+
+        _ = heap.insert(9);
+        assert_eq!((Some(&3), Some(&9)), (heap.peek_min(), heap.peek_max()));
+
+        assert_eq!(Some(9), heap.extract_max());
+        assert_eq!(Some(5), heap.extract_max());
+        assert_eq!(Some(3), heap.extract_max());
+        assert_eq!(None, heap.extract_max());
+    }
+
+    #[test]
+    fn extract_min_all() {
+        let nums = pseudo_random(200);
+        let mut heap = FixMinMaxHeap::new(7);
+
+        for n in nums.iter() {
+            assert_eq!(Ok(()), heap.insert(*n));
+            verify(&heap);
+        }
+
+        let mut criterion = nums;
+        criterion.sort();
+
+        for n in criterion {
+            assert_eq!(Some(n), heap.extract_min());
+            verify(&heap);
+        }
+
+        assert_eq!(None, heap.extract_min());
+    }
+
+    #[test]
+    fn extract_max_all() {
+        let nums = pseudo_random(200);
+        let mut heap = FixMinMaxHeap::new(7);
+
+        for n in nums.iter() {
+            _ = heap.insert(*n);
+        }
+
+        let mut criterion = nums;
+        criterion.sort();
+
+        for n in criterion.into_iter().rev() {
+            assert_eq!(Some(n), heap.extract_max());
+            verify(&heap);
+        }
+    }
+
+    #[test]
+    fn alternating() {
+        let nums = pseudo_random(255);
+        let mut heap = FixMinMaxHeap::new(7);
+
+        for n in nums.iter() {
+            _ = heap.insert(*n);
+        }
+
+        let mut criterion = nums;
+        criterion.sort();
+
+        let (mut lo, mut hi) = (0, criterion.len());
+        while lo < hi {
+            if (lo + hi) % 2 == 0 {
+                assert_eq!(Some(criterion[lo]), heap.extract_min());
+                lo += 1;
+            } else {
+                hi -= 1;
+                assert_eq!(Some(criterion[hi]), heap.extract_max());
+            }
+
+            verify(&heap);
+        }
+
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn drops_items() {
+        use std::rc::Rc;
+
+        let rc = Rc::new(());
+        let mut heap = FixMinMaxHeap::new(2);
+        for n in 0..5 {
+            _ = heap.insert((n, Rc::clone(&rc)));
+        }
+
+        _ = heap.extract_max();
+        assert_eq!(5, Rc::strong_count(&rc));
+
+        drop(heap);
+        assert_eq!(1, Rc::strong_count(&rc));
+    }
+
+    #[test]
+    fn is_min_level_test() {
+        let levels = [0, 1, 2, 6, 7, 14, 15, 30].map(is_min_level);
+        assert_eq!([true, false, false, true, false, false, true, true], levels);
+    }
+
+    #[test]
+    fn keep_best() {
+        let nums = pseudo_random(1_000);
+        let mut best = KeepBest::new(10);
+
+        for n in nums.iter() {
+            let full = best.len() == 10;
+            assert_eq!(full, best.offer(*n).is_some());
+        }
+
+        assert_eq!(10, best.len());
+
+        let mut criterion = nums;
+        criterion.sort_by(|a, b| b.cmp(a));
+
+        assert_eq!(Some(&criterion[0]), best.peek_best());
+        assert_eq!(Some(&criterion[9]), best.peek_worst());
+        assert_eq!(criterion[..10], best.into_sorted_vec());
+    }
+
+    #[test]
+    fn keep_best_eviction() {
+        let mut best = KeepBest::new(2);
+
+        assert_eq!(None, best.offer(5));
+        assert_eq!(None, best.offer(3));
+        // not better than worst
+        assert_eq!(Some(3), best.offer(3));
+        assert_eq!(Some(1), best.offer(1));
+        // evicts worst
+        assert_eq!(Some(3), best.offer(7));
+
+        assert_eq!(vec![7, 5], best.into_sorted_vec());
+    }
+
+    #[test]
+    fn keep_least() {
+        use std::cmp::Reverse;
+
+        let mut least = KeepBest::new(3);
+        for n in [4, 8, 1, 9, 2, 7] {
+            least.offer(Reverse(n));
+        }
+
+        let least = least.into_sorted_vec().into_iter().map(|r| r.0);
+        assert_eq!(vec![1, 2, 4], least.collect::<Vec<i32>>());
+    }
+
+    #[test]
+    fn keep_zero() {
+        let mut best = KeepBest::new(0);
+        assert_eq!(Some(1), best.offer(1));
+        assert!(best.is_empty());
+        assert_eq!(None, best.peek_best());
+    }
+}
@@ -1,8 +1,15 @@
+use std::cmp::Ordering;
+
 // TC: Ο(n log n)
 pub fn heapsort<T>(data: &mut [T], inv_ord: bool)
 where
     T: PartialOrd + Clone,
 {
+    let data_len = data.len();
+    if data_len < 2 {
+        return;
+    }
+
     let sort_form = if inv_ord {
         SortForm::Minimal
     } else {
@@ -10,7 +17,6 @@ where
     };
     let cmp = cmp::<T>(sort_form);
 
-    let data_len = data.len();
     let mut ix = (data_len / 2) - 1;
 
     // TC: Ο(n log n)
@@ -66,6 +72,277 @@ where
     }
 }
 
+/// Comparator observed by sorts
+pub trait SortCmp<T> {
+    fn is_less(&mut self, a: &T, b: &T) -> bool;
+
+    /// Called on each swap of two items.
+    fn swapped(&mut self) {}
+}
+
+impl<T, F> SortCmp<T> for F
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    fn is_less(&mut self, a: &T, b: &T) -> bool {
+        self(a, b) == Ordering::Less
+    }
+}
+
+/// Counts comparisons and swaps of wrapped comparator use
+pub struct Counting<C> {
+    cmp: C,
+    comparisons: usize,
+    swaps: usize,
+}
+
+impl<C> Counting<C> {
+    pub fn new(cmp: C) -> Self {
+        Counting {
+            cmp,
+            comparisons: 0,
+            swaps: 0,
+        }
+    }
+
+    pub fn comparisons(&self) -> usize {
+        self.comparisons
+    }
+
+    pub fn swaps(&self) -> usize {
+        self.swaps
+    }
+}
+
+impl<T, C> SortCmp<T> for Counting<C>
+where
+    C: SortCmp<T>,
+{
+    fn is_less(&mut self, a: &T, b: &T) -> bool {
+        self.comparisons += 1;
+        self.cmp.is_less(a, b)
+    }
+
+    fn swapped(&mut self) {
+        self.swaps += 1;
+        self.cmp.swapped();
+    }
+}
+
+/// Heap construction strategy
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Construction {
+    /// items are inserted one by one, sifted up
+    /// <br/>TC: Ο(n log n)
+    Williams,
+    /// subtrees are heapified from last parent to root, sifted down
+    /// <br/>TC: Ο(n)
+    Floyd,
+}
+
+/// Sift-down strategy, used by construction too when applicable
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SiftDown {
+    /// greater child is compared with sifted item on each level,
+    /// 2 comparisons per level
+    Standard,
+    /// path of greater children is followed to leaf, then climbed back
+    /// to sifted item position, ~1 comparison per level since sifted item,
+    /// former last leaf, usually belongs near bottom
+    BottomUp,
+}
+
+/// Sorts ascending by `cmp`, in-place, without cloning.
+/// <br/>TC: Ο(n log n), SC: Ο(1)
+pub fn heapsort_by<T, F>(data: &mut [T], mut cmp: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    heapsort_with(data, Construction::Floyd, SiftDown::Standard, &mut cmp);
+}
+
+/// Sorts ascending by key `f` extracts, `f` is called Ο(n log n) times.
+pub fn heapsort_by_key<T, K, F>(data: &mut [T], mut f: F)
+where
+    K: Ord,
+    F: FnMut(&T) -> K,
+{
+    heapsort_by(data, |a, b| f(a).cmp(&f(b)));
+}
+
+/// Sorts ascending by `cmp` with selected strategies.
+/// <br/>Wrap `cmp` into `Counting` to obtain comparison and swap counts.
+pub fn heapsort_with<T, C>(data: &mut [T], construction: Construction, sift: SiftDown, cmp: &mut C)
+where
+    C: SortCmp<T>,
+{
+    let len = data.len();
+
+    match construction {
+        Construction::Williams => {
+            for ix in 1..len {
+                sift_up(data, ix, cmp);
+            }
+        }
+        Construction::Floyd => build_heap(data, len, sift, cmp),
+    }
+
+    sort_heap(data, len, sift, cmp);
+}
+
+/// Leaves `k` smallest items sorted at front, order of rest is unspecified.
+/// <br/>`k` greater than `data.len()` sorts whole `data`.
+/// <br/>TC: Ο(n log k), SC: Ο(1)
+pub fn partial_heapsort<T>(data: &mut [T], k: usize)
+where
+    T: Ord,
+{
+    partial_heapsort_by(data, k, T::cmp);
+}
+
+/// `partial_heapsort` ordered by `cmp`.
+pub fn partial_heapsort_by<T, F>(data: &mut [T], k: usize, mut cmp: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let cmp = &mut cmp;
+    let k = k.min(data.len());
+
+    if k == 0 {
+        return;
+    }
+
+    // maximal heap of `k` smallest items seen so far
+    build_heap(data, k, SiftDown::Standard, cmp);
+
+    for ix in k..data.len() {
+        if cmp.is_less(&data[ix], &data[0]) {
+            swap(data, 0, ix, cmp);
+            sift_down(data, k, 0, cmp);
+        }
+    }
+
+    sort_heap(data, k, SiftDown::Standard, cmp);
+}
+
+fn swap<T, C>(data: &mut [T], a: usize, b: usize, cmp: &mut C)
+where
+    C: SortCmp<T>,
+{
+    data.swap(a, b);
+    cmp.swapped();
+}
+
+// maximal heap in `data[..heap_len]`
+fn build_heap<T, C>(data: &mut [T], heap_len: usize, sift: SiftDown, cmp: &mut C)
+where
+    C: SortCmp<T>,
+{
+    for ix in (0..heap_len / 2).rev() {
+        match sift {
+            SiftDown::Standard => sift_down(data, heap_len, ix, cmp),
+            SiftDown::BottomUp => sift_down_bottom_up(data, heap_len, ix, cmp),
+        }
+    }
+}
+
+// moves maximum behind shrinking heap
+fn sort_heap<T, C>(data: &mut [T], heap_len: usize, sift: SiftDown, cmp: &mut C)
+where
+    C: SortCmp<T>,
+{
+    for ix in (1..heap_len).rev() {
+        swap(data, 0, ix, cmp);
+
+        match sift {
+            SiftDown::Standard => sift_down(data, ix, 0, cmp),
+            SiftDown::BottomUp => sift_down_bottom_up(data, ix, 0, cmp),
+        }
+    }
+}
+
+fn sift_up<T, C>(data: &mut [T], mut des_ix: usize, cmp: &mut C)
+where
+    C: SortCmp<T>,
+{
+    while des_ix > 0 {
+        let pred_ix = (des_ix - 1) / 2;
+
+        if !cmp.is_less(&data[pred_ix], &data[des_ix]) {
+            break;
+        }
+
+        swap(data, pred_ix, des_ix, cmp);
+        des_ix = pred_ix;
+    }
+}
+
+fn sift_down<T, C>(data: &mut [T], heap_len: usize, mut pred_ix: usize, cmp: &mut C)
+where
+    C: SortCmp<T>,
+{
+    loop {
+        let mut des_ix = 2 * pred_ix + 1;
+
+        if des_ix >= heap_len {
+            break;
+        }
+
+        let des2_ix = des_ix + 1;
+        if des2_ix < heap_len && cmp.is_less(&data[des_ix], &data[des2_ix]) {
+            des_ix = des2_ix;
+        }
+
+        if !cmp.is_less(&data[pred_ix], &data[des_ix]) {
+            break;
+        }
+
+        swap(data, pred_ix, des_ix, cmp);
+        pred_ix = des_ix;
+    }
+}
+
+// Wegener's bottom-up sift
+fn sift_down_bottom_up<T, C>(data: &mut [T], heap_len: usize, root_ix: usize, cmp: &mut C)
+where
+    C: SortCmp<T>,
+{
+    // leaf reached via greater descendants
+    let mut ix = root_ix;
+    loop {
+        let des_ix = 2 * ix + 1;
+
+        if des_ix >= heap_len {
+            break;
+        }
+
+        let des2_ix = des_ix + 1;
+        ix = if des2_ix < heap_len && cmp.is_less(&data[des_ix], &data[des2_ix]) {
+            des2_ix
+        } else {
+            des_ix
+        };
+    }
+
+    // climbing back to first item not less than sifted one
+    while ix > root_ix && cmp.is_less(&data[ix], &data[root_ix]) {
+        ix = (ix - 1) / 2;
+    }
+
+    // sifted item moves to `ix`, items on path to it move one level up,
+    // path is read from 1-based index bits
+    let target = ix + 1;
+    let depth = target.ilog2() - (root_ix + 1).ilog2();
+
+    let mut pred_ix = root_ix;
+    for level in (0..depth).rev() {
+        let des_ix = (target >> level) - 1;
+
+        swap(data, pred_ix, des_ix, cmp);
+        pred_ix = des_ix;
+    }
+}
+
 enum SortForm {
     Minimal,
     Maximal,
@@ -84,6 +361,19 @@ where
 #[cfg(test)]
 mod tests_of_units {
 
+    // xorshift sequence, same for each call
+    fn pseudo_random(len: usize, modulo: u32) -> Vec<u32> {
+        let mut seed = 0x9e37_79b9_u32;
+        (0..len)
+            .map(|_| {
+                seed ^= seed << 13;
+                seed ^= seed >> 17;
+                seed ^= seed << 5;
+                seed % modulo
+            })
+            .collect()
+    }
+
     mod heapsort {
 
         use super::super::heapsort;
@@ -109,6 +399,252 @@ mod tests_of_units {
 
             assert_eq!(criterion, data);
         }
+
+        #[test]
+        fn short() {
+            let mut empty: [u8; 0] = [];
+            heapsort(&mut empty, false);
+
+            let mut one = [1];
+            heapsort(&mut one, true);
+            assert_eq!([1], one);
+        }
+    }
+
+    mod heapsort_by {
+        use super::super::{heapsort_by, heapsort_by_key};
+        use super::pseudo_random;
+
+        // neither `Clone` nor `PartialOrd`
+        #[derive(PartialEq, Debug)]
+        struct Rec {
+            id: u8,
+            name: String,
+        }
+
+        fn recs() -> Vec<Rec> {
+            [(3, "c"), (1, "d"), (2, "a"), (5, "e"), (4, "b")]
+                .into_iter()
+                .map(|(id, name)| Rec {
+                    id,
+                    name: name.to_string(),
+                })
+                .collect()
+        }
+
+        #[test]
+        fn by() {
+            let mut data = [100, 30, -5, 12, 25, -3, -33, 0, 0, 3, 16, 22];
+            heapsort_by(&mut data, |a, b| b.cmp(a));
+
+            let criterion = [100, 30, 25, 22, 16, 12, 3, 0, 0, -3, -5, -33];
+            assert_eq!(criterion, data);
+        }
+
+        #[test]
+        fn by_key() {
+            let mut data = recs();
+
+            heapsort_by_key(&mut data, |r| r.id);
+            assert_eq!(
+                vec![1, 2, 3, 4, 5],
+                data.iter().map(|r| r.id).collect::<Vec<u8>>()
+            );
+
+            heapsort_by_key(&mut data, |r| r.name.clone());
+            let names = data.iter().map(|r| r.name.as_str()).collect::<Vec<&str>>();
+            assert_eq!(vec!["a", "b", "c", "d", "e"], names);
+        }
+
+        #[test]
+        fn short() {
+            let mut empty: [Rec; 0] = [];
+            heapsort_by(&mut empty, |a, b| a.id.cmp(&b.id));
+
+            let mut one = [3];
+            heapsort_by_key(&mut one, |x| *x);
+            assert_eq!([3], one);
+        }
+
+        #[test]
+        fn random_data() {
+            for len in 0..64 {
+                let mut data = pseudo_random(len, 32);
+
+                let mut criterion = data.clone();
+                criterion.sort();
+
+                heapsort_by(&mut data, u32::cmp);
+                assert_eq!(criterion, data);
+            }
+        }
+    }
+
+    mod heapsort_with {
+        use super::super::{
+            build_heap, heapsort_with, sift_down_bottom_up, sift_up, Construction, Counting,
+            SiftDown,
+        };
+        use super::pseudo_random;
+
+        const STRATEGIES: [(Construction, SiftDown); 4] = [
+            (Construction::Williams, SiftDown::Standard),
+            (Construction::Williams, SiftDown::BottomUp),
+            (Construction::Floyd, SiftDown::Standard),
+            (Construction::Floyd, SiftDown::BottomUp),
+        ];
+
+        #[test]
+        fn strategies() {
+            for (construction, sift) in STRATEGIES {
+                for len in 0..70 {
+                    let mut data = pseudo_random(len, 16);
+
+                    let mut criterion = data.clone();
+                    criterion.sort();
+
+                    heapsort_with(&mut data, construction, sift, &mut u32::cmp);
+                    assert_eq!(criterion, data, "{:?} {:?}", construction, sift);
+                }
+            }
+        }
+
+        #[test]
+        fn counting() {
+            let mut data = [1, 2, 3];
+            let mut cmp = Counting::new(u32::cmp);
+
+            heapsort_with(&mut data, Construction::Floyd, SiftDown::Standard, &mut cmp);
+
+            assert_eq!([1, 2, 3], data);
+            assert_eq!(3, cmp.comparisons());
+            assert_eq!(4, cmp.swaps());
+
+            let mut cmp = Counting::new(u32::cmp);
+            heapsort_with(
+                &mut [7],
+                Construction::Williams,
+                SiftDown::BottomUp,
+                &mut cmp,
+            );
+
+            assert_eq!(0, cmp.comparisons());
+            assert_eq!(0, cmp.swaps());
+        }
+
+        #[test]
+        fn bottom_up_saves_comparisons() {
+            let data = pseudo_random(1_000, 1_000_000);
+
+            let mut counts = Vec::new();
+            for sift in [SiftDown::Standard, SiftDown::BottomUp] {
+                let mut data = data.clone();
+                let mut cmp = Counting::new(u32::cmp);
+
+                heapsort_with(&mut data, Construction::Floyd, sift, &mut cmp);
+                counts.push(cmp.comparisons());
+            }
+
+            // ~2n log n versus ~n log n
+            assert!(counts[1] * 3 < counts[0] * 2, "{:?}", counts);
+        }
+
+        #[test]
+        fn floyd_builds_cheaper() {
+            let data = pseudo_random(1_000, 1_000_000);
+            let len = data.len();
+
+            let mut williams = data.clone();
+            let mut williams_cmp = Counting::new(u32::cmp);
+            for ix in 1..len {
+                sift_up(&mut williams, ix, &mut williams_cmp);
+            }
+
+            let mut floyd = data.clone();
+            let mut floyd_cmp = Counting::new(u32::cmp);
+            build_heap(&mut floyd, len, SiftDown::Standard, &mut floyd_cmp);
+
+            for heap in [williams, floyd] {
+                assert!((1..len).all(|ix| heap[(ix - 1) / 2] >= heap[ix]));
+            }
+
+            // Floyd construction stays under 2n comparisons
+            assert!(floyd_cmp.comparisons() < 2 * len);
+            assert!(floyd_cmp.swaps() < williams_cmp.swaps());
+        }
+
+        #[test]
+        fn bottom_up_sift() {
+            #[rustfmt::skip]
+            let test_cases = [
+                // root, heap, criterion
+                (0, vec![1, 9, 8, 7, 6, 5, 4], vec![9, 7, 8, 1, 6, 5, 4]),
+                (0, vec![8, 9, 7, 3, 2, 6, 5], vec![9, 8, 7, 3, 2, 6, 5]),
+                (0, vec![9, 3, 7, 1, 2, 6, 5], vec![9, 3, 7, 1, 2, 6, 5]),
+                (1, vec![9, 1, 7, 5, 6, 0, 0], vec![9, 6, 7, 5, 1, 0, 0]),
+            ];
+
+            for (root, mut heap, criterion) in test_cases {
+                let len = heap.len();
+                sift_down_bottom_up(&mut heap, len, root, &mut u32::cmp);
+
+                assert_eq!(criterion, heap);
+            }
+        }
+    }
+
+    mod partial_heapsort {
+        use super::super::{partial_heapsort, partial_heapsort_by};
+        use super::pseudo_random;
+
+        #[test]
+        fn basic_test() {
+            let mut data = [100, 30, -5, 12, 25, -3, -33, 0, 0, 3, 16, 22];
+            partial_heapsort(&mut data, 4);
+
+            assert_eq!([-33, -5, -3, 0], data[..4]);
+
+            let mut rest = data[4..].to_vec();
+            rest.sort();
+            assert_eq!(vec![0, 3, 12, 16, 22, 25, 30, 100], rest);
+        }
+
+        #[test]
+        fn k_bounds() {
+            let mut data = [3, 1, 2];
+
+            partial_heapsort(&mut data, 0);
+            assert_eq!([3, 1, 2], data);
+
+            partial_heapsort(&mut data, 10);
+            assert_eq!([1, 2, 3], data);
+
+            let mut empty: [u8; 0] = [];
+            partial_heapsort(&mut empty, 2);
+        }
+
+        #[test]
+        fn by() {
+            let mut data = ["bb", "a", "dddd", "ccc", "eeeee"];
+            partial_heapsort_by(&mut data, 2, |a, b| b.len().cmp(&a.len()));
+
+            assert_eq!(["eeeee", "dddd"], data[..2]);
+        }
+
+        #[test]
+        fn random_data() {
+            let data = pseudo_random(100, 50);
+
+            let mut criterion = data.clone();
+            criterion.sort();
+
+            for k in 0..=data.len() {
+                let mut data = data.clone();
+                partial_heapsort(&mut data, k);
+
+                assert_eq!(criterion[..k], data[..k]);
+            }
+        }
     }
 
     mod bubble_down {
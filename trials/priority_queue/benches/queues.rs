@@ -0,0 +1,105 @@
+//! cargo bench --bench queues
+//!
+//! compares `vec_queue::Queue`, `NextingList`, `BucketQueue` and heap `PriorityQueue`
+//! across sizes on 3 insert/extract mixes
+//! - `balanced` — 1 insertion per 1 extraction
+//! - `insert_heavy` — 4 insertions per 1 extraction
+//! - `fill_drain` — all insertions, then all extractions
+//!
+//! Queue construction is part of measurement.
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use min_heap_priority_queue::{self as heap, Prio};
+use priority_queue::bucket_queue::BucketQueue;
+use priority_queue::nexting_list::NextingList;
+use priority_queue::vec_queue::Queue;
+use priority_queue::PriorityQueue;
+
+const MAX_PRIO: usize = 1023;
+
+const SIZES: [usize; 3] = [1 << 6, 1 << 9, 1 << 12];
+
+// insertions per 1 extraction
+const MIXES: [(&str, usize); 3] = [
+    ("balanced", 1),
+    ("insert_heavy", 4),
+    ("fill_drain", usize::MAX),
+];
+
+// xorshift for determinism
+fn pseudo_random(len: usize) -> Vec<usize> {
+    let mut x = 0x9E37_79B9_7F4A_7C15u64;
+    (0..len)
+        .map(|_| {
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            (x % (MAX_PRIO as u64 + 1)) as usize
+        })
+        .collect()
+}
+
+// levels of heap with room for `size` items
+fn levels(size: usize) -> usize {
+    (usize::BITS - size.leading_zeros()) as usize
+}
+
+// `ratio` insertions per 1 extraction, rest is drained at end
+fn workload<Q>(mut queue: Q, prios: &[usize], ratio: usize) -> u64
+where
+    Q: PriorityQueue<u64>,
+{
+    let mut sum = 0u64;
+
+    for (ix, p) in prios.iter().enumerate() {
+        _ = queue.enqueue(*p as u64, *p);
+
+        if (ix + 1) % ratio == 0 {
+            sum = sum.wrapping_add(queue.dequeue().unwrap());
+        }
+    }
+
+    while let Some(v) = queue.dequeue() {
+        sum = sum.wrapping_add(v);
+    }
+
+    sum
+}
+
+fn queues(c: &mut Criterion) {
+    for (mix, ratio) in MIXES {
+        let mut group = c.benchmark_group(mix);
+
+        for size in SIZES {
+            let prios = pseudo_random(size);
+
+            group.bench_with_input(BenchmarkId::new("vec_queue", size), &prios, |b, prios| {
+                b.iter(|| workload(Queue::new(), black_box(prios), ratio))
+            });
+
+            group.bench_with_input(
+                BenchmarkId::new("nexting_list", size),
+                &prios,
+                |b, prios| b.iter(|| workload(NextingList::new(), black_box(prios), ratio)),
+            );
+
+            group.bench_with_input(
+                BenchmarkId::new("bucket_queue", size),
+                &prios,
+                |b, prios| b.iter(|| workload(BucketQueue::new(MAX_PRIO), black_box(prios), ratio)),
+            );
+
+            group.bench_with_input(BenchmarkId::new("heap", size), &prios, |b, prios| {
+                b.iter(|| {
+                    let queue = heap::PriorityQueue::<Prio<usize, u64>>::new(levels(size));
+                    workload(queue, black_box(prios), ratio)
+                })
+            });
+        }
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, queues);
+criterion_main!(benches);
@@ -0,0 +1,113 @@
+use std::time::Instant;
+
+pub use min_heap_priority_queue::aging::{AgeUnit, Aging, Exponential, Linear, Stepped, WaitStats};
+
+/// No boost, priorities are static.
+#[derive(Debug)]
+pub struct NoAging;
+
+impl Aging for NoAging {
+    fn boost(&self, _: u64) -> u64 {
+        0
+    }
+
+    fn ages(&self) -> bool {
+        false
+    }
+}
+
+/// Enqueue moment
+#[derive(Clone, Copy, Debug)]
+pub struct Stamp {
+    dequeues: u64,
+    at: Instant,
+}
+
+/// Waiting clock of queue
+#[derive(Debug)]
+pub struct AgeClock {
+    unit: AgeUnit,
+    stats: WaitStats,
+}
+
+impl AgeClock {
+    pub fn new(unit: AgeUnit) -> Self {
+        AgeClock {
+            unit,
+            stats: WaitStats::default(),
+        }
+    }
+
+    pub fn stamp(&self) -> Stamp {
+        Stamp {
+            dequeues: self.stats.dequeues,
+            at: Instant::now(),
+        }
+    }
+
+    /// Waiting in `AgeUnit`.
+    pub fn waited(&self, stamp: &Stamp, now: Instant) -> u64 {
+        match self.unit {
+            AgeUnit::Dequeues => self.stats.dequeues - stamp.dequeues,
+            AgeUnit::Time(unit) => {
+                let waited = now.saturating_duration_since(stamp.at);
+                (waited.as_nanos() / unit.as_nanos().max(1)) as u64
+            }
+        }
+    }
+
+    /// Records dequeue of item stamped with `stamp`.
+    pub fn dequeued(&mut self, stamp: &Stamp) {
+        let stats = &mut self.stats;
+
+        stats.max_dequeues = stats.max_dequeues.max(stats.dequeues - stamp.dequeues);
+        stats.max_time = stats.max_time.max(stamp.at.elapsed());
+        stats.dequeues += 1;
+    }
+
+    pub fn stats(&self) -> &WaitStats {
+        &self.stats
+    }
+}
+
+#[cfg(test)]
+mod tests_of_units {
+    use super::{AgeClock, AgeUnit, Aging, Linear, NoAging};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn no_aging() {
+        assert_eq!(0, NoAging.boost(u64::MAX));
+        assert!(!NoAging.ages());
+        assert!(Linear(0).ages());
+    }
+
+    #[test]
+    fn clock() {
+        let mut clock = AgeClock::new(AgeUnit::Dequeues);
+        let stamp = clock.stamp();
+
+        for _ in 0..3 {
+            let s = clock.stamp();
+            clock.dequeued(&s);
+        }
+
+        assert_eq!(3, clock.waited(&stamp, Instant::now()));
+
+        clock.dequeued(&stamp);
+        assert_eq!(4, clock.stats().dequeues);
+        assert_eq!(3, clock.stats().max_dequeues);
+    }
+
+    #[test]
+    fn time_unit() {
+        let clock = AgeClock::new(AgeUnit::Time(Duration::from_millis(10)));
+        let stamp = clock.stamp();
+
+        assert_eq!(0, clock.waited(&stamp, stamp.at + Duration::from_millis(9)));
+        assert_eq!(
+            2,
+            clock.waited(&stamp, stamp.at + Duration::from_millis(25))
+        );
+    }
+}
@@ -0,0 +1,211 @@
+use crate::PriorityQueue;
+use std::collections::VecDeque;
+
+const WORD: usize = u64::BITS as usize;
+
+/// Monotone priority queue for bounded integer priorities
+/// # Summary
+/// As in `bucketsort_by_val`, there is one bucket per priority `0..=max`.
+/// Buckets are FIFO, occupancy bitmap points to nonempty ones. Cursor
+/// remembers last popped priority, nothing lesser is searched for.
+/// <br/>`push` TC: Ο(1)
+/// <br/>`pop`, `peek` TC: Ο(1) amortized when pushed priorities do not
+/// go below last popped one, Ο(max / 64) worst
+/// <br/>SC: Ο(n + max)
+pub struct BucketQueue<T> {
+    buckets: Vec<VecDeque<T>>,
+    // bit per bucket, set for nonempty
+    occupied: Vec<u64>,
+    cursor: usize,
+    len: usize,
+}
+
+impl<T> BucketQueue<T> {
+    /// Priorities `0..=max` are supported.
+    pub fn new(max: usize) -> BucketQueue<T> {
+        let buckets_len = max + 1;
+
+        BucketQueue {
+            buckets: (0..buckets_len).map(|_| VecDeque::new()).collect(),
+            occupied: vec![0; buckets_len.div_ceil(WORD)],
+            cursor: 0,
+            len: 0,
+        }
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        let ix = self.next_occupied()?;
+        self.buckets[ix].front()
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let ix = self.next_occupied()?;
+        self.cursor = ix;
+
+        let bucket = &mut self.buckets[ix];
+        let val = bucket.pop_front();
+
+        if bucket.is_empty() {
+            self.occupied[ix / WORD] &= !(1 << (ix % WORD));
+        }
+
+        self.len -= 1;
+        val
+    }
+
+    /// Equal priorities are popped in push order.
+    pub fn push(&mut self, val: T, prio: usize) {
+        assert!(prio < self.buckets.len(), "Priority exceeds maximum.");
+
+        self.buckets[prio].push_back(val);
+        self.occupied[prio / WORD] |= 1 << (prio % WORD);
+
+        // non-monotone push
+        if prio < self.cursor {
+            self.cursor = prio;
+        }
+
+        self.len += 1;
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // first nonempty bucket from cursor on
+    fn next_occupied(&self) -> Option<usize> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let cursor = self.cursor;
+        let occupied = &self.occupied;
+
+        let mut word_ix = cursor / WORD;
+        let mut word = occupied[word_ix] & (u64::MAX << (cursor % WORD));
+
+        while word == 0 {
+            word_ix += 1;
+            word = occupied[word_ix];
+        }
+
+        Some(word_ix * WORD + word.trailing_zeros() as usize)
+    }
+}
+
+impl<T> PriorityQueue<T> for BucketQueue<T> {
+    /// Panics when `prio` exceeds maximum.
+    fn enqueue(&mut self, val: T, prio: usize) -> Result<(), ()> {
+        self.push(val, prio);
+        Ok(())
+    }
+
+    fn peek(&self) -> Option<&T> {
+        BucketQueue::peek(self)
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+#[cfg(test)]
+mod tests_of_units {
+    use super::BucketQueue;
+
+    #[test]
+    fn new() {
+        let queue = BucketQueue::<u8>::new(1023);
+
+        assert_eq!(1024, queue.buckets.len());
+        assert_eq!(16, queue.occupied.len());
+        assert!(queue.is_empty());
+        assert_eq!(None, queue.peek());
+
+        let queue = BucketQueue::<u8>::new(64);
+        assert_eq!(2, queue.occupied.len());
+    }
+
+    #[test]
+    fn basic_test() {
+        let mut queue = BucketQueue::new(1023);
+
+        for (val, prio) in [(3, 700), (0, 0), (2, 64), (4, 1023), (1, 63)] {
+            queue.push(val, prio);
+        }
+
+        assert_eq!(5, queue.len());
+
+        for val in 0..5 {
+            assert_eq!(Some(&val), queue.peek());
+            assert_eq!(Some(val), queue.pop());
+        }
+
+        assert_eq!(None, queue.pop());
+        assert_eq!([0; 16], queue.occupied[..]);
+    }
+
+    #[test]
+    fn fifo_ties() {
+        let mut queue = BucketQueue::new(10);
+
+        for val in 0..5 {
+            queue.push(val, 7);
+        }
+
+        for val in 0..5 {
+            assert_eq!(Some(val), queue.pop());
+        }
+    }
+
+    #[test]
+    fn monotone() {
+        let mut queue = BucketQueue::new(100);
+        queue.push(0, 0);
+
+        // each pop pushes successors, never below popped priority
+        let mut popped = Vec::new();
+        while let Some(prio) = queue.pop() {
+            popped.push(prio);
+
+            if prio + 30 <= 100 {
+                queue.push(prio + 30, prio + 30);
+                queue.push(prio + 7, prio + 7);
+            }
+
+            assert!(queue.cursor <= prio);
+        }
+
+        assert!(popped.windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    #[test]
+    fn non_monotone_push() {
+        let mut queue = BucketQueue::new(200);
+
+        queue.push('b', 150);
+        queue.push('c', 190);
+        assert_eq!(Some('b'), queue.pop());
+        assert_eq!(150, queue.cursor);
+
+        queue.push('a', 5);
+        assert_eq!(Some(&'a'), queue.peek());
+        assert_eq!(Some('a'), queue.pop());
+        assert_eq!(Some('c'), queue.pop());
+    }
+
+    #[test]
+    #[should_panic(expected = "Priority exceeds maximum.")]
+    fn priority_over_max() {
+        let mut queue = BucketQueue::new(10);
+        queue.push(0, 11);
+    }
+}
@@ -1,2 +1,102 @@
-mod nexting_list;
-mod vec_queue;
+//! Priority queue trials
+//!
+//! - `vec_queue::Queue` — unsorted `Vec`, Ο(n) `dequeue`
+//! - `bucket_queue::BucketQueue` — bucket per integer priority, Ο(1) amortized both
+//! - `nexting_list::NextingList` — sorted linked list, Ο(n) `enqueue`
+//! - `min_heap_priority_queue::PriorityQueue` — fixed binary heap, Ο(log n) both
+//!
+//! All are usable via `PriorityQueue` trait.
+
+pub mod aging;
+pub mod bucket_queue;
+pub mod nexting_list;
+pub mod vec_queue;
+
+use min_heap_priority_queue as heap;
+use min_heap_priority_queue::Prio;
+
+/// Common priority queue interface
+/// <br/>Lesser `prio` is dequeued first. Order of equal priorities is
+/// implementation specific.
+pub trait PriorityQueue<T> {
+    /// Returns `Err(())` when queue is full.
+    fn enqueue(&mut self, val: T, prio: usize) -> Result<(), ()>;
+
+    fn peek(&self) -> Option<&T>;
+
+    fn dequeue(&mut self) -> Option<T>;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> PriorityQueue<T> for heap::PriorityQueue<Prio<usize, T>>
+where
+    T: Clone + Default,
+{
+    fn enqueue(&mut self, val: T, prio: usize) -> Result<(), ()> {
+        self.insert(Prio(prio, val))
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.peek_root().map(|p| &p.1)
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        self.extract_root().map(|p| p.1)
+    }
+
+    fn len(&self) -> usize {
+        heap::PriorityQueue::len(self)
+    }
+}
+
+#[cfg(test)]
+mod tests_of_units {
+    use super::bucket_queue::BucketQueue;
+    use super::nexting_list::NextingList;
+    use super::vec_queue::Queue;
+    use super::{heap, PriorityQueue};
+    use min_heap_priority_queue::Prio;
+
+    fn test<Q>(mut queue: Q)
+    where
+        Q: PriorityQueue<u32>,
+    {
+        assert!(queue.is_empty());
+        assert_eq!(None, queue.peek());
+
+        for (val, prio) in [(30, 3), (10, 1), (50, 5), (20, 2), (40, 4)] {
+            assert_eq!(Ok(()), queue.enqueue(val, prio));
+        }
+
+        assert_eq!(5, queue.len());
+        assert_eq!(Some(&10), queue.peek());
+
+        for val in [10, 20, 30, 40, 50] {
+            assert_eq!(Some(val), queue.dequeue());
+        }
+
+        assert_eq!(None, queue.dequeue());
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn common_behavior() {
+        test(Queue::new());
+        test(NextingList::new());
+        test(BucketQueue::new(1023));
+        test(heap::PriorityQueue::<Prio<usize, u32>>::new(2));
+    }
+
+    #[test]
+    fn heap_full() {
+        let mut queue = heap::PriorityQueue::<Prio<usize, u32>>::new(0);
+
+        assert_eq!(Ok(()), queue.enqueue(1, 1));
+        assert_eq!(Err(()), queue.enqueue(2, 2));
+    }
+}
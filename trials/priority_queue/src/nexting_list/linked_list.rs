@@ -1,18 +1,26 @@
-#![allow(dead_code)]
+use crate::aging::{AgeClock, AgeUnit, Aging, NoAging, Stamp, WaitStats};
+use crate::PriorityQueue;
+use std::time::Instant;
 
 #[derive(Debug)]
 struct Nodule<T> {
     val: T,
     prio: usize,
+    stamp: Stamp,
     next: Option<Box<Nodule<T>>>,
 }
 
+/// Priority queue realized
+/// via linked list sorted on insertion
 #[derive(Debug)]
-struct NextingList<T>
+pub struct NextingList<T, A = NoAging>
 where
     T: Default,
 {
     pseudo_head: Box<Nodule<T>>,
+    len: usize,
+    aging: A,
+    clock: AgeClock,
 }
 
 impl<T> NextingList<T>
@@ -20,41 +28,78 @@ where
     T: Default,
 {
     pub fn new() -> NextingList<T> {
+        NextingList::with_aging(NoAging, AgeUnit::Dequeues)
+    }
+}
+
+impl<T> Default for NextingList<T>
+where
+    T: Default,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, A> NextingList<T, A>
+where
+    T: Default,
+    A: Aging,
+{
+    /// Effective priority is `prio` lowered by `aging` boost of waiting in `unit`.
+    /// <br/>Aging `peek` and `pop` TC: Ο(n)
+    pub fn with_aging(aging: A, unit: AgeUnit) -> NextingList<T, A> {
+        let clock = AgeClock::new(unit);
+
         NextingList {
             pseudo_head: Box::new(Nodule {
                 val: T::default(),
                 prio: usize::default(),
+                stamp: clock.stamp(),
                 next: None,
             }),
+            len: 0,
+            aging,
+            clock,
         }
     }
 
     pub fn peek(&self) -> Option<&T> {
-        let head = &self.pseudo_head.next;
-        if head.is_some() {
-            Some(&head.as_ref().unwrap().val)
-        } else {
-            None
+        let ix = self.best_ix()?;
+
+        let mut nod = self.pseudo_head.next.as_ref().unwrap();
+        for _ in 0..ix {
+            nod = nod.next.as_ref().unwrap();
         }
+
+        Some(&nod.val)
     }
 
     pub fn pop(&mut self) -> Option<T> {
-        let head = &mut self.pseudo_head.next;
-        if let Some(mut h) = head.take() {
-            *head = h.next.take();
-            Some((*h).val)
-        } else {
-            None
+        let ix = self.best_ix()?;
+
+        let mut curr_nod = &mut self.pseudo_head;
+        for _ in 0..ix {
+            curr_nod = curr_nod.next.as_mut().unwrap();
         }
+
+        let mut nod = curr_nod.next.take().unwrap();
+        curr_nod.next = nod.next.take();
+
+        self.len -= 1;
+        self.clock.dequeued(&nod.stamp);
+        Some(nod.val)
     }
 
     pub fn push(&mut self, val: T, prio: usize) {
         let mut nod = Box::new(Nodule {
             val,
             prio,
+            stamp: self.clock.stamp(),
             next: None,
         });
 
+        self.len += 1;
         let mut curr_nod = &mut self.pseudo_head;
 
         loop {
@@ -74,6 +119,71 @@ where
             curr_nod = next.as_mut().unwrap();
         }
     }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Waiting of popped items.
+    pub fn stats(&self) -> &WaitStats {
+        self.clock.stats()
+    }
+
+    // index of item of least effective priority, earlier item wins ties
+    fn best_ix(&self) -> Option<usize> {
+        let mut curr = &self.pseudo_head.next;
+
+        if !self.aging.ages() {
+            return curr.as_ref().map(|_| 0);
+        }
+
+        let now = Instant::now();
+        let mut best: Option<(usize, i128)> = None;
+
+        let mut ix = 0;
+        while let Some(nod) = curr {
+            let waited = self.clock.waited(&nod.stamp, now);
+            // boost beyond `prio` still counts
+            let eff = nod.prio as i128 - self.aging.boost(waited) as i128;
+
+            match best {
+                Some((_, best_eff)) if best_eff <= eff => {}
+                _ => best = Some((ix, eff)),
+            }
+
+            ix += 1;
+            curr = &nod.next;
+        }
+
+        best.map(|(ix, _)| ix)
+    }
+}
+
+impl<T, A> PriorityQueue<T> for NextingList<T, A>
+where
+    T: Default,
+    A: Aging,
+{
+    fn enqueue(&mut self, val: T, prio: usize) -> Result<(), ()> {
+        self.push(val, prio);
+        Ok(())
+    }
+
+    fn peek(&self) -> Option<&T> {
+        NextingList::peek(self)
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
 }
 
 #[cfg(test)]
@@ -165,4 +275,90 @@ mod units_of_test {
         assert!(list.peek().is_none());
         assert!(list.pop().is_none());
     }
+
+    mod aging {
+        use super::super::NextingList;
+        use crate::aging::{AgeUnit, Linear, Stepped};
+        use std::time::Duration;
+
+        // steady high priority load, 1 item in, 1 item out
+        fn load<A>(list: &mut NextingList<usize, A>, rounds: usize) -> Option<usize>
+        where
+            A: crate::aging::Aging,
+        {
+            for round in 0..rounds {
+                list.push(1, 0);
+
+                if list.pop() == Some(9) {
+                    return Some(round);
+                }
+            }
+
+            None
+        }
+
+        #[test]
+        fn no_aging_starves() {
+            let mut list = NextingList::<usize>::new();
+            list.push(9, 10);
+
+            assert_eq!(None, load(&mut list, 100));
+            assert_eq!(0, list.stats().max_dequeues);
+        }
+
+        #[test]
+        fn linear() {
+            let mut list = NextingList::with_aging(Linear(1), AgeUnit::Dequeues);
+            list.push(9, 10);
+
+            // 11 dequeues lower priority 10 below 0,
+            // on tie item nearer to head wins
+            assert_eq!(Some(11), load(&mut list, 100));
+            assert_eq!(11, list.stats().max_dequeues);
+            assert_eq!(12, list.stats().dequeues);
+        }
+
+        #[test]
+        fn stepped() {
+            let aging = Stepped::new(4, 5);
+            let mut list = NextingList::with_aging(aging, AgeUnit::Dequeues);
+            list.push(9, 10);
+
+            assert_eq!(Some(12), load(&mut list, 100));
+        }
+
+        #[test]
+        fn peek_follows_aging() {
+            let mut list = NextingList::with_aging(|w| w * 5, AgeUnit::Dequeues);
+
+            list.push(9, 10);
+            list.push(1, 1);
+
+            assert_eq!(Some(&1), list.peek());
+            assert_eq!(Some(1), list.pop());
+
+            // 9 is at 5
+            list.push(2, 3);
+            assert_eq!(Some(&2), list.peek());
+            assert_eq!(Some(2), list.pop());
+
+            // 9 is at 0
+            list.push(3, 3);
+            assert_eq!(Some(&9), list.peek());
+            assert_eq!(Some(9), list.pop());
+
+            assert_eq!(Some(3), list.pop());
+            assert_eq!(None, list.peek());
+        }
+
+        #[test]
+        fn time_unit() {
+            let unit = AgeUnit::Time(Duration::from_secs(3600));
+            let mut list = NextingList::with_aging(Linear(1), unit);
+            list.push(9, 10);
+
+            assert_eq!(None, load(&mut list, 20));
+            assert!(list.stats().max_time < Duration::from_secs(3600));
+        }
+    }
 }
@@ -1 +1,3 @@
 mod linked_list;
+
+pub use linked_list::NextingList;
@@ -1,8 +1,8 @@
-#![allow(dead_code)]
-
 use std::cmp::{Eq, PartialOrd};
 use std::fmt::Debug;
 
+use crate::PriorityQueue;
+
 #[derive(Debug)]
 struct Item<T>
 where
@@ -12,7 +12,9 @@ where
     prio: usize,
 }
 
-struct Queue<T>
+/// Priority queue realized
+/// via unsorted vector scanned on dequeue
+pub struct Queue<T>
 where
     T: PartialOrd + Eq + Debug,
 {
@@ -71,6 +73,37 @@ where
     }
 }
 
+impl<T> Default for Queue<T>
+where
+    T: PartialOrd + Eq + Debug,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> PriorityQueue<T> for Queue<T>
+where
+    T: PartialOrd + Eq + Debug,
+{
+    fn enqueue(&mut self, val: T, prio: usize) -> Result<(), ()> {
+        self.enq(val, prio);
+        Ok(())
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.peek_ix().map(|ix| &self.items[ix].val)
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        self.deq()
+    }
+
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+}
+
 #[cfg(test)]
 mod tests_of_units {
     use super::*;